@@ -0,0 +1,78 @@
+//! Embeds build metadata for `eagle version --verbose` as `EAGLE_BUILD_*`
+//! env vars; `src/build_info.rs` reads them back with `env!`.
+
+use std::path::Path;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn main() {
+	let git = |args: &[&str]| {
+		Command::new("git")
+			.args(args)
+			.output()
+			.ok()
+			.filter(|o| o.status.success())
+			.map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+	};
+
+	let commit = git(&["rev-parse", "--short=12", "HEAD"]).unwrap_or_default();
+	let dirty = git(&["status", "--porcelain", "--untracked-files=no"])
+		.is_some_and(|s| !s.is_empty());
+
+	// Honors SOURCE_DATE_EPOCH so reproducible builds stay reproducible.
+	let timestamp = std::env::var("SOURCE_DATE_EPOCH")
+		.ok()
+		.and_then(|v| v.parse::<u64>().ok())
+		.unwrap_or_else(|| {
+			SystemTime::now()
+				.duration_since(UNIX_EPOCH)
+				.map(|d| d.as_secs())
+				.unwrap_or(0)
+		});
+
+	let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+	let rustc_version = Command::new(rustc)
+		.arg("--version")
+		.output()
+		.ok()
+		.map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+		.unwrap_or_default();
+
+	let mut features: Vec<String> = std::env::vars()
+		.filter_map(|(key, _)| {
+			key.strip_prefix("CARGO_FEATURE_")
+				.map(|f| f.to_lowercase().replace('_', "-"))
+		})
+		.collect();
+	features.sort();
+
+	let env = |key: &str| std::env::var(key).unwrap_or_default();
+	println!("cargo:rustc-env=EAGLE_BUILD_COMMIT={commit}");
+	println!("cargo:rustc-env=EAGLE_BUILD_DIRTY={dirty}");
+	println!("cargo:rustc-env=EAGLE_BUILD_TIMESTAMP={timestamp}");
+	println!("cargo:rustc-env=EAGLE_BUILD_TARGET={}", env("TARGET"));
+	println!("cargo:rustc-env=EAGLE_BUILD_PROFILE={}", env("PROFILE"));
+	println!("cargo:rustc-env=EAGLE_BUILD_RUSTC={rustc_version}");
+	println!(
+		"cargo:rustc-env=EAGLE_BUILD_FEATURES={}",
+		features.join(",")
+	);
+
+	// Rerun when the checked-out commit or the sources change, so the
+	// commit and dirty flag stay current. Watching a missing path would
+	// rerun this on every build.
+	println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+	println!("cargo:rerun-if-changed=build.rs");
+	println!("cargo:rerun-if-changed=src");
+	for path in [".git/HEAD", ".git/index"] {
+		if Path::new(path).exists() {
+			println!("cargo:rerun-if-changed={path}");
+		}
+	}
+	if let Some(head_ref) = git(&["symbolic-ref", "-q", "HEAD"]) {
+		let path = format!(".git/{head_ref}");
+		if Path::new(&path).exists() {
+			println!("cargo:rerun-if-changed={path}");
+		}
+	}
+}
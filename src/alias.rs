@@ -0,0 +1,158 @@
+//! User-defined command aliases from the `[alias]` config table.
+//!
+//! An alias maps a new command name to a command line, git-style:
+//! `mcs = "minecraft create --type paper"`. Aliases are expanded before clap
+//! parses the arguments and may never shadow a built-in command or alias.
+
+use std::ffi::OsString;
+
+use clap::Command;
+
+use crate::config::Config;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserAlias {
+	pub name: String,
+	pub expansion: Vec<String>,
+}
+
+/// The aliases from the config, split into usable ones and ones rejected
+/// with a reason.
+#[derive(Debug, Clone, Default)]
+pub struct Aliases {
+	pub entries: Vec<UserAlias>,
+	pub rejected: Vec<(String, String)>,
+}
+
+impl Aliases {
+	/// Reads `[alias]` from the config and validates every entry against the
+	/// built-in command names and aliases of `cli`. Never fails: this runs
+	/// before clap, so a bad entry must not break `--help` or usage errors.
+	pub fn load(config: &Config, cli: &Command) -> Self {
+		let mut out = Self::default();
+
+		let Some(value) = config.get("alias") else {
+			return out;
+		};
+		let Some(table) = value.as_table() else {
+			out.rejected
+				.push(("alias".to_string(), "must be a table".to_string()));
+			return out;
+		};
+
+		for (name, value) in table {
+			let Some(line) = value.as_str() else {
+				out.rejected
+					.push((name.clone(), "value must be a string".to_string()));
+				continue;
+			};
+
+			match validate(name, line, cli) {
+				Ok(expansion) => out.entries.push(UserAlias {
+					name: name.clone(),
+					expansion,
+				}),
+				Err(reason) => out.rejected.push((name.clone(), reason)),
+			}
+		}
+
+		out
+	}
+
+	pub fn find(&self, name: &str) -> Option<&UserAlias> {
+		self.entries.iter().find(|a| a.name == name)
+	}
+
+	/// Renders the aliases as an extra help section, if there are any.
+	pub fn help_section(&self) -> Option<String> {
+		if self.entries.is_empty() {
+			return None;
+		}
+
+		let width = self.entries.iter().map(|a| a.name.len()).max()?;
+		let lines: Vec<String> = self
+			.entries
+			.iter()
+			.map(|a| format!("  {:<width$}  {}", a.name, a.expansion.join(" ")))
+			.collect();
+		Some(format!("User aliases:\n{}", lines.join("\n")))
+	}
+}
+
+/// Checks that `name` is free and `line` starts with a built-in command.
+/// Returns the split expansion.
+#[doc(hidden)]
+pub fn validate(
+	name: &str,
+	line: &str,
+	cli: &Command,
+) -> Result<Vec<String>, String> {
+	if name.is_empty() || name.starts_with('-') {
+		return Err("alias names must not be empty or start with '-'".into());
+	}
+
+	if let Some(sub) = find_builtin(cli, name) {
+		return Err(format!(
+			"'{name}' is already the built-in command '{}'",
+			sub.get_name()
+		));
+	}
+
+	let expansion =
+		crate::util::split_command_line(line).map_err(|e| e.to_string())?;
+	let first = expansion
+		.first()
+		.ok_or_else(|| "expansion is empty".to_string())?;
+	if find_builtin(cli, first).is_none() {
+		return Err(format!("'{first}' is not a built-in command"));
+	}
+
+	Ok(expansion)
+}
+
+fn find_builtin<'a>(cli: &'a Command, name: &str) -> Option<&'a Command> {
+	cli.get_subcommands().find(|sub| {
+		sub.get_name() == name || sub.get_all_aliases().any(|a| a == name)
+	})
+}
+
+/// Replaces the first command word of `args` (after the program name and any
+/// leading global flags) with its alias expansion. Returns the args unchanged
+/// when that word is not an alias.
+#[doc(hidden)]
+pub fn expand(args: Vec<OsString>, aliases: &Aliases) -> Vec<OsString> {
+	let Some(index) = command_index(&args) else {
+		return args;
+	};
+	let Some(alias) = args
+		.get(index)
+		.and_then(|a| a.to_str())
+		.and_then(|name| aliases.find(name))
+	else {
+		return args;
+	};
+
+	let mut out = Vec::with_capacity(args.len() + alias.expansion.len());
+	out.extend(args.iter().take(index).cloned());
+	out.extend(alias.expansion.iter().map(OsString::from));
+	out.extend(args.iter().skip(index + 1).cloned());
+	out
+}
+
+/// Global flags that take a separate value (`--color always`).
+const VALUE_FLAGS: &[&str] = &["--color"];
+
+fn command_index(args: &[OsString]) -> Option<usize> {
+	let mut index = 1;
+	while let Some(arg) = args.get(index) {
+		let arg = arg.to_str()?;
+		if arg == "--" {
+			return None;
+		}
+		if !arg.starts_with('-') {
+			return Some(index);
+		}
+		index += if VALUE_FLAGS.contains(&arg) { 2 } else { 1 };
+	}
+	None
+}
@@ -0,0 +1,65 @@
+//! Metadata about how this binary was built, embedded by `build.rs`.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BuildInfo {
+	/// Short commit hash, empty when built outside a git checkout.
+	pub commit: &'static str,
+	/// Whether tracked files differed from `commit`.
+	pub dirty: bool,
+	/// Unix seconds when the build script ran (or `SOURCE_DATE_EPOCH`).
+	pub timestamp: u64,
+	pub target: &'static str,
+	/// `debug` or `release`.
+	pub profile: &'static str,
+	/// `rustc --version` of the compiler used.
+	pub rustc: &'static str,
+	/// Enabled cargo features, comma-separated.
+	pub features: &'static str,
+}
+
+impl BuildInfo {
+	pub const CURRENT: Self = Self {
+		commit: env!("EAGLE_BUILD_COMMIT"),
+		dirty: matches!(env!("EAGLE_BUILD_DIRTY").as_bytes(), b"true"),
+		timestamp: parse_u64(env!("EAGLE_BUILD_TIMESTAMP")),
+		target: env!("EAGLE_BUILD_TARGET"),
+		profile: env!("EAGLE_BUILD_PROFILE"),
+		rustc: env!("EAGLE_BUILD_RUSTC"),
+		features: env!("EAGLE_BUILD_FEATURES"),
+	};
+
+	/// `abc123def456`, `abc123def456-dirty`, or `unknown`.
+	pub fn commit_label(&self) -> String {
+		match (self.commit, self.dirty) {
+			("", _) => "unknown".to_string(),
+			(commit, true) => format!("{commit}-dirty"),
+			(commit, false) => commit.to_string(),
+		}
+	}
+
+	/// The build time as RFC 3339 in UTC.
+	pub fn built_at(&self) -> String {
+		i64::try_from(self.timestamp)
+			.ok()
+			.and_then(|t| time::OffsetDateTime::from_unix_timestamp(t).ok())
+			.and_then(|t| {
+				t.format(&time::format_description::well_known::Rfc3339)
+					.ok()
+			})
+			.unwrap_or_else(|| "unknown".to_string())
+	}
+
+	pub fn feature_list(&self) -> Vec<&'static str> {
+		self.features.split(',').filter(|f| !f.is_empty()).collect()
+	}
+}
+
+const fn parse_u64(s: &str) -> u64 {
+	let mut rest = s.as_bytes();
+	let mut value = 0_u64;
+	while let [digit @ b'0'..=b'9', tail @ ..] = rest {
+		value = value * 10 + (*digit - b'0') as u64;
+		rest = tail;
+	}
+	value
+}
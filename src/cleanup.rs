@@ -0,0 +1,151 @@
+//! Cleanup of half-written files and folders on Ctrl-C.
+//!
+//! Drop guards never run when the process is killed by a signal (and the
+//! release profile aborts on panic), so code that creates something it
+//! would have to undo registers the path here while the work is in flight.
+//! The handler from [`install`] removes every registered path on SIGINT or
+//! SIGTERM before eagle exits, unless a command [`catch`]es the interrupt.
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+static PENDING: Mutex<Vec<(u64, PathBuf)>> = Mutex::new(Vec::new());
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+static CATCHING: AtomicBool = AtomicBool::new(false);
+static CAUGHT: AtomicBool = AtomicBool::new(false);
+
+/// A path that is removed if eagle is interrupted. Dropping it unregisters
+/// the path without touching it.
+#[derive(Debug)]
+#[must_use = "the path is unregistered as soon as this is dropped"]
+pub struct Registration {
+	id: u64,
+}
+
+impl Drop for Registration {
+	fn drop(&mut self) {
+		if let Ok(mut pending) = PENDING.lock() {
+			pending.retain(|(id, _)| *id != self.id);
+		}
+	}
+}
+
+/// Registers a file or folder for removal on interrupt.
+pub fn register(path: &Path) -> Registration {
+	let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+	if let Ok(mut pending) = PENDING.lock() {
+		pending.push((id, path.to_path_buf()));
+	}
+	Registration { id }
+}
+
+/// Returns the registered paths, oldest first.
+pub fn pending() -> Vec<PathBuf> {
+	PENDING
+		.lock()
+		.map(|p| p.iter().map(|(_, path)| path.clone()).collect())
+		.unwrap_or_default()
+}
+
+/// Removes every registered path, newest first, and clears the registry.
+pub fn run() {
+	let Ok(mut pending) = PENDING.lock() else {
+		return;
+	};
+	for (_, path) in pending.drain(..).rev() {
+		let _ = if path.is_dir() {
+			std::fs::remove_dir_all(&path)
+		} else {
+			std::fs::remove_file(&path)
+		};
+	}
+}
+
+/// Installs the SIGINT/SIGTERM handler: it runs [`run`], then `exit`, which
+/// is expected to end the process. While [`catch`] is in effect it only
+/// records the interrupt.
+pub fn install(exit: impl Fn() + Send + 'static) -> anyhow::Result<()> {
+	ctrlc::set_handler(move || {
+		if CATCHING.load(Ordering::SeqCst) {
+			CAUGHT.store(true, Ordering::SeqCst);
+			return;
+		}
+		run();
+		exit();
+	})?;
+	Ok(())
+}
+
+/// Keeps interrupts from ending eagle while held, for commands that run
+/// until Ctrl-C and should then return normally, like `logs --follow`.
+#[derive(Debug)]
+#[must_use = "interrupts end eagle again as soon as this is dropped"]
+pub struct Catch {
+	_private: (),
+}
+
+/// Starts catching interrupts; poll [`Catch::wait`] to notice one.
+pub fn catch() -> Catch {
+	CAUGHT.store(false, Ordering::SeqCst);
+	CATCHING.store(true, Ordering::SeqCst);
+	Catch { _private: () }
+}
+
+impl Catch {
+	/// Sleeps for up to `timeout`; returns early with `true` once an
+	/// interrupt came in.
+	pub fn wait(&self, timeout: Duration) -> bool {
+		let deadline = Instant::now() + timeout;
+		loop {
+			if CAUGHT.load(Ordering::SeqCst) {
+				return true;
+			}
+			let left = deadline.saturating_duration_since(Instant::now());
+			if left.is_zero() {
+				return false;
+			}
+			std::thread::sleep(left.min(Duration::from_millis(25)));
+		}
+	}
+}
+
+impl Drop for Catch {
+	fn drop(&mut self) {
+		CATCHING.store(false, Ordering::SeqCst);
+	}
+}
+
+/// Removes a folder when dropped unless [`DirGuard::commit`] was called, and
+/// registers it for removal on interrupt meanwhile.
+pub struct DirGuard {
+	path: PathBuf,
+	committed: bool,
+	_registration: Registration,
+}
+
+impl DirGuard {
+	pub fn new(path: PathBuf) -> Self {
+		let registration = register(&path);
+		Self {
+			path,
+			committed: false,
+			_registration: registration,
+		}
+	}
+
+	pub fn commit(&mut self) {
+		self.committed = true;
+	}
+}
+
+impl Drop for DirGuard {
+	fn drop(&mut self) {
+		if self.committed {
+			return;
+		}
+
+		let _ = std::fs::remove_dir_all(&self.path);
+	}
+}
@@ -1,6 +1,12 @@
+use std::ffi::OsString;
+
 use clap::{Arg, ArgAction, Command};
 
-use crate::commands;
+use crate::commands::{self, Category, CommandSpec, Stability};
+use crate::process::{Native, ProcessRunner};
+
+/// `--timings` without a file: print the breakdown table.
+pub const TIMINGS_TABLE: &str = "table";
 
 pub fn build_cli() -> Command {
 	let mut cmd = Command::new("eagle")
@@ -9,17 +15,157 @@ pub fn build_cli() -> Command {
 		.disable_version_flag(true)
 		.arg(
 			Arg::new("version")
-				.short('v')
+				.short('V')
 				.long("version")
 				.action(ArgAction::Version)
 				.help("Print version"),
 		)
+		.arg(
+			Arg::new("json")
+				.long("json")
+				.global(true)
+				.action(ArgAction::SetTrue)
+				.help("Print machine-readable JSON output"),
+		)
+		.arg(
+			Arg::new("quiet")
+				.short('q')
+				.long("quiet")
+				.global(true)
+				.action(ArgAction::SetTrue)
+				.conflicts_with("verbose")
+				.help("Only print errors"),
+		)
+		.arg(
+			Arg::new("verbose")
+				.short('v')
+				.long("verbose")
+				.global(true)
+				.action(ArgAction::Count)
+				.help("Print debug output (-vv for trace: HTTP, subprocesses)"),
+		)
+		.arg(
+			Arg::new("dry_run")
+				.long("dry-run")
+				.global(true)
+				.action(ArgAction::SetTrue)
+				.help("Print what would change instead of doing it"),
+		)
+		.arg(
+			Arg::new("non_interactive")
+				.long("non-interactive")
+				.global(true)
+				.action(ArgAction::SetTrue)
+				.help("Never prompt; fail when input is missing (default when stdin is not a terminal)"),
+		)
+		.arg(
+			Arg::new("timings")
+				.long("timings")
+				.value_name("FILE")
+				.global(true)
+				.num_args(0..=1)
+				.require_equals(true)
+				.default_missing_value(TIMINGS_TABLE)
+				.help("Print where the time went; --timings=FILE writes a Chrome trace instead"),
+		)
+		.arg(
+			Arg::new("color")
+				.long("color")
+				.value_name("WHEN")
+				.global(true)
+				.value_parser(["auto", "always", "never"])
+				.help("When to use colors (honors NO_COLOR / CLICOLOR_FORCE)"),
+		)
 		.version(env!("CARGO_PKG_VERSION"))
-		.arg_required_else_help(true);
+		.arg_required_else_help(true)
+		.allow_external_subcommands(true)
+		.external_subcommand_value_parser(clap::value_parser!(OsString));
 
 	for spec in commands::iter_specs() {
-		cmd = cmd.subcommand((spec.command)());
+		let sub = (spec.command)();
+		cmd = cmd.subcommand(sub.hide(spec.stability == Stability::Hidden));
 	}
 
-	cmd
+	let template = help_template(&cmd, None);
+	cmd.help_template(template)
+}
+
+/// `cmd` with the root help also noting which commands miss a tool. That
+/// looks each tool up on `PATH`, so only call it when help gets printed.
+pub fn with_tool_marks(cmd: Command) -> Command {
+	let template = help_template(&cmd, Some(&Native));
+	cmd.help_template(template)
+}
+
+/// Root help with the commands grouped by [`Category`] instead of clap's
+/// single flat list.
+fn help_template(
+	cmd: &Command,
+	processes: Option<&dyn ProcessRunner>,
+) -> String {
+	let styles = cmd.get_styles();
+	let header = styles.get_header();
+	let literal = styles.get_literal();
+
+	let mut specs: Vec<&CommandSpec> = commands::iter_specs()
+		.into_iter()
+		.filter(|s| s.stability != Stability::Hidden)
+		.collect();
+	specs.sort_by_key(|s| s.name);
+	let width = specs.iter().map(|s| s.name.len()).max().unwrap_or(0);
+
+	let mut groups = String::new();
+	for category in Category::ALL {
+		let members: Vec<&&CommandSpec> =
+			specs.iter().filter(|s| s.category == category).collect();
+		if members.is_empty() {
+			continue;
+		}
+
+		groups
+			.push_str(&format!("{header}{}:{header:#}\n", category.heading()));
+		for spec in members {
+			let about = find_subcommand(cmd, spec.name)
+				.and_then(|sub| sub.get_about())
+				.map(|a| a.to_string())
+				.unwrap_or_default();
+			let marks = spec.marks(processes);
+			let marks = if marks.is_empty() {
+				String::new()
+			} else {
+				format!(" [{}]", marks.join("; "))
+			};
+			groups.push_str(&format!(
+				"  {literal}{:width$}{literal:#}  {about}{marks}\n",
+				spec.name
+			));
+		}
+		groups.push('\n');
+	}
+
+	format!(
+		"{{before-help}}{{about-with-newline}}\n\
+		 {{usage-heading}} {{usage}}\n\n\
+		 {groups}\
+		 {header}Options:{header:#}\n{{options}}{{after-help}}"
+	)
+}
+
+/// Names and aliases of `cmd`'s direct subcommands.
+pub fn subcommand_names(cmd: &Command) -> impl Iterator<Item = &str> {
+	cmd.get_subcommands().flat_map(|sub| {
+		std::iter::once(sub.get_name()).chain(sub.get_all_aliases())
+	})
+}
+
+/// Finds a direct subcommand of `cmd` by name or alias, ignoring case.
+pub fn find_subcommand<'a>(
+	cmd: &'a Command,
+	name: &str,
+) -> Option<&'a Command> {
+	cmd.get_subcommands().find(|sub| {
+		std::iter::once(sub.get_name())
+			.chain(sub.get_all_aliases())
+			.any(|n| n.eq_ignore_ascii_case(name))
+	})
 }
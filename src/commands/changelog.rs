@@ -0,0 +1,66 @@
+use clap::{Arg, ArgMatches, Command};
+
+use crate::commands::update::{self, Channel, notes};
+use crate::commands::{Category, CommandSpec, Example, Stability};
+use crate::context::Context;
+use crate::error;
+use crate::net::Endpoints;
+use crate::ui;
+
+fn build() -> Command {
+	Command::new("changelog")
+		.about("Show the release notes of releases newer than this one")
+		.arg(
+			Arg::new("since")
+				.long("since")
+				.value_name("VERSION")
+				.help("Start after this version instead of the installed one"),
+		)
+}
+
+fn run(matches: &ArgMatches, ctx: &Context) -> anyhow::Result<()> {
+	let since = matches
+		.get_one::<String>("since")
+		.map_or(ctx.version, String::as_str);
+	let version = update::parse_version(since).ok_or_else(|| {
+		error::usage(format!(
+			"Invalid version '{since}' (expected e.g. 3.4.0 or v3.4.0-beta.1)"
+		))
+	})?;
+
+	let endpoints = Endpoints::from_config(&ctx.config)?;
+	let channel = Channel::from_config(&ctx.config)?;
+	let changes = notes::since(&endpoints, channel, &version)?;
+
+	if changes.is_empty() {
+		ui::success(&format!("No releases after v{version}"));
+	}
+	notes::print(&changes);
+	ui::result(serde_json::json!({
+		"since": version.to_string(),
+		"releases": notes::to_json(&changes),
+	}));
+	Ok(())
+}
+
+inventory::submit! {
+	CommandSpec {
+		name: "changelog",
+		command: build,
+		run,
+		examples: &[
+			Example {
+				command: "eagle changelog",
+				about: "See what the next update brings",
+			},
+			Example {
+				command: "eagle changelog --since 3.3.0",
+				about: "Read the notes of every release after 3.3.0",
+			},
+		],
+		category: Category::SelfManagement,
+		platforms: &[],
+		tools: &[],
+		stability: Stability::Stable,
+	}
+}
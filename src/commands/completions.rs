@@ -0,0 +1,59 @@
+use clap::{Arg, ArgMatches, Command};
+use clap_complete::env::Shells;
+
+use crate::commands::{Category, CommandSpec, Example, Stability};
+use crate::context::Context;
+
+const SHELLS: [&str; 5] = ["bash", "zsh", "fish", "powershell", "elvish"];
+
+fn build() -> Command {
+	Command::new("completions")
+		.about("Print a shell completion script")
+		.long_about(
+			"Print a shell completion script.\n\n\
+			 The script calls back into eagle on <TAB>, so server names and\n\
+			 templates from your config are completed as they are now.\n\n\
+			 bash:  echo 'source <(eagle completions bash)' >> ~/.bashrc\n\
+			 zsh:   echo 'source <(eagle completions zsh)' >> ~/.zshrc\n\
+			 fish:  eagle completions fish > ~/.config/fish/completions/eagle.fish",
+		)
+		.arg(
+			Arg::new("shell")
+				.help("Target shell")
+				.value_parser(SHELLS)
+				.required(true),
+		)
+}
+
+fn run(matches: &ArgMatches, ctx: &Context) -> anyhow::Result<()> {
+	let shell = matches
+		.get_one::<String>("shell")
+		.ok_or_else(|| anyhow::anyhow!("missing <shell>"))?;
+
+	let shells = Shells::builtins();
+	let completer = shells
+		.completer(shell)
+		.ok_or_else(|| anyhow::anyhow!("Unsupported shell: {shell}"))?;
+
+	let exe = ctx.exe_path.to_string_lossy();
+	let mut out = std::io::stdout();
+	completer
+		.write_registration("COMPLETE", "eagle", "eagle", &exe, &mut out)?;
+	Ok(())
+}
+
+inventory::submit! {
+	CommandSpec {
+		name: "completions",
+		command: build,
+		run,
+		examples: &[Example {
+			command: "echo 'source <(eagle completions zsh)' >> ~/.zshrc",
+			about: "Enable zsh completions in new shells",
+		}],
+		category: Category::Setup,
+		platforms: &[],
+		tools: &[],
+		stability: Stability::Stable,
+	}
+}
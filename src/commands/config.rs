@@ -0,0 +1,225 @@
+use clap::{Arg, ArgMatches, Command};
+
+use crate::commands::{Category, CommandSpec, Example, Stability};
+use crate::config::{self, Config};
+use crate::context::Context;
+use crate::ui;
+
+fn build() -> Command {
+	let key = || Arg::new("key").help("Dotted config key").required(true);
+
+	Command::new("config")
+		.about("Read or change persistent settings")
+		.subcommand_required(true)
+		.arg_required_else_help(true)
+		.subcommand(
+			Command::new("get")
+				.about("Print the value stored for a key")
+				.arg(key()),
+		)
+		.subcommand(
+			Command::new("set")
+				.about("Store a value for a key")
+				.arg(key())
+				.arg(Arg::new("value").help("New value").required(true)),
+		)
+		.subcommand(Command::new("unset").about("Remove a key").arg(key()))
+		.subcommand(
+			Command::new("list").about("List stored values").arg(
+				Arg::new("known")
+					.long("known")
+					.help("List all known keys instead")
+					.action(clap::ArgAction::SetTrue),
+			),
+		)
+		.subcommand(Command::new("path").about("Print the config file path"))
+		.subcommand(
+			Command::new("edit").about("Open the config file in $EDITOR"),
+		)
+}
+
+fn run(matches: &ArgMatches, ctx: &Context) -> anyhow::Result<()> {
+	match matches.subcommand() {
+		Some(("get", sub)) => run_get(sub, &ctx.config),
+		Some(("set", sub)) => run_set(sub, ctx),
+		Some(("unset", sub)) => run_unset(sub, ctx),
+		Some(("list", sub)) => run_list(sub, &ctx.config),
+		Some(("path", _)) => {
+			let path = ctx.config.path();
+			if ui::json_enabled() {
+				ui::result(serde_json::json!({ "path": path }));
+			} else {
+				println!("{}", path.display());
+			}
+			Ok(())
+		}
+		Some(("edit", _)) => run_edit(ctx),
+		Some((other, _)) => anyhow::bail!("Unknown subcommand: {other}"),
+		None => anyhow::bail!("missing config subcommand"),
+	}
+}
+
+fn required<'a>(matches: &'a ArgMatches, id: &str) -> anyhow::Result<&'a str> {
+	matches
+		.get_one::<String>(id)
+		.map(String::as_str)
+		.ok_or_else(|| anyhow::anyhow!("missing <{id}>"))
+}
+
+fn run_get(matches: &ArgMatches, config: &Config) -> anyhow::Result<()> {
+	let key = required(matches, "key")?;
+	if config::key_spec(key).is_none() {
+		anyhow::bail!("Unknown config key '{key}'");
+	}
+
+	let value = config
+		.get(key)
+		.ok_or_else(|| anyhow::anyhow!("'{key}' is not set"))?;
+	if ui::json_enabled() {
+		ui::result(serde_json::json!({ "key": key, "value": value }));
+	} else {
+		println!("{}", display_value(value));
+	}
+	Ok(())
+}
+
+fn run_set(matches: &ArgMatches, ctx: &Context) -> anyhow::Result<()> {
+	let key = required(matches, "key")?;
+	let value = required(matches, "value")?;
+
+	if let Some(name) = key.strip_prefix("alias.") {
+		crate::alias::validate(name, value, &crate::cli::build_cli())
+			.map_err(|reason| anyhow::anyhow!("Invalid alias: {reason}"))?;
+	}
+
+	let mut config = ctx.config.clone();
+	config.set(key, value)?;
+	config.save_with(ctx.exec.as_ref())?;
+
+	ui::success(&format!("Set {key} = {value}"));
+	ui::result(serde_json::json!({ "key": key, "value": config.get(key) }));
+	Ok(())
+}
+
+fn run_unset(matches: &ArgMatches, ctx: &Context) -> anyhow::Result<()> {
+	let key = required(matches, "key")?;
+
+	let mut config = ctx.config.clone();
+	if !config.unset(key) {
+		ui::muted(&format!("'{key}' was not set"));
+		ui::result(serde_json::json!({ "key": key, "removed": false }));
+		return Ok(());
+	}
+	config.save_with(ctx.exec.as_ref())?;
+
+	ui::success(&format!("Unset {key}"));
+	ui::result(serde_json::json!({ "key": key, "removed": true }));
+	Ok(())
+}
+
+fn run_list(matches: &ArgMatches, config: &Config) -> anyhow::Result<()> {
+	if matches.get_flag("known") {
+		if ui::json_enabled() {
+			let keys: Vec<_> = config::KEYS
+				.iter()
+				.map(|spec| {
+					serde_json::json!({
+						"key": spec.key,
+						"env": spec.env,
+						"description": spec.description,
+					})
+				})
+				.collect();
+			ui::result(serde_json::json!({ "keys": keys }));
+			return Ok(());
+		}
+
+		for spec in config::KEYS {
+			let env =
+				spec.env.map(|e| format!(" (env: {e})")).unwrap_or_default();
+			println!("{}{env}", spec.key);
+			ui::muted(&format!("    {}", spec.description));
+		}
+		return Ok(());
+	}
+
+	let entries = config.entries();
+	if ui::json_enabled() {
+		let values: serde_json::Map<_, _> = entries
+			.into_iter()
+			.map(|(key, value)| (key, serde_json::json!(value)))
+			.collect();
+		ui::result(serde_json::json!({ "values": values }));
+		return Ok(());
+	}
+
+	if entries.is_empty() {
+		ui::muted(&format!("No values set in {}", config.path().display()));
+		return Ok(());
+	}
+
+	for (key, value) in entries {
+		println!("{key} = {}", display_value(&value));
+	}
+	Ok(())
+}
+
+fn run_edit(ctx: &Context) -> anyhow::Result<()> {
+	let path = ctx.config.path();
+	if !path.exists() {
+		ctx.config.save_with(ctx.exec.as_ref())?;
+	}
+
+	let editor = std::env::var("VISUAL")
+		.or_else(|_| std::env::var("EDITOR"))
+		.unwrap_or_else(|_| "vi".to_string());
+	let mut parts = editor.split_whitespace();
+	let program = parts
+		.next()
+		.ok_or_else(|| anyhow::anyhow!("$EDITOR is set but empty"))?;
+
+	let path_s = path.to_string_lossy();
+	let mut args: Vec<&str> = parts.collect();
+	args.push(&path_s);
+
+	let status = ctx.exec.run_inherit(program, &args, None)?;
+	if !status.success() {
+		return Err(crate::error::process_failed(program, status));
+	}
+
+	Config::load(path)?;
+	Ok(())
+}
+
+fn display_value(value: &toml::Value) -> String {
+	match value {
+		toml::Value::String(s) => s.clone(),
+		other => other.to_string(),
+	}
+}
+
+inventory::submit! {
+	CommandSpec {
+		name: "config",
+		command: build,
+		run,
+		examples: &[
+			Example {
+				command: "eagle config set minecraft.ram_mb 4096",
+				about: "Store a default",
+			},
+			Example {
+				command: "eagle config get minecraft.ram_mb",
+				about: "Print a stored value",
+			},
+			Example {
+				command: "eagle config list --known",
+				about: "List every known key with its env var",
+			},
+		],
+		category: Category::Setup,
+		platforms: &[],
+		tools: &[],
+		stability: Stability::Stable,
+	}
+}
@@ -1,12 +1,45 @@
 use std::path::PathBuf;
 
+use anyhow::Context as _;
 use clap::{Arg, ArgMatches, Command};
+use clap_complete::engine::{ArgValueCandidates, CompletionCandidate};
 use dialoguer::{Input, Select};
 
-use crate::commands::CommandSpec;
+use crate::cleanup::DirGuard;
+use crate::commands::{BUN, Category, CommandSpec, Example, GIT, Stability};
+use crate::config::{self, Config};
 use crate::context::Context;
+use crate::error;
+use crate::paths::Paths;
+use crate::timings;
 use crate::ui;
-use crate::util;
+
+const BUILTIN_TEMPLATES: &[(&str, &str, &str)] = &[
+	(
+		"discord",
+		"discord",
+		"https://github.com/meowlounge/discord-template.git",
+	),
+	(
+		"next",
+		"frontend",
+		"https://github.com/meowlounge/next-template.git",
+	),
+	(
+		"typescript",
+		"typescript",
+		"https://github.com/meowlounge/typescript-template.git",
+	),
+];
+
+/// A project template: the repo to clone and the subfolder of the create
+/// root it is cloned into.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+	pub name: String,
+	pub dir: String,
+	pub repo: String,
+}
 
 fn build() -> Command {
 	Command::new("create")
@@ -23,146 +56,214 @@ fn build() -> Command {
 			Arg::new("template")
 				.long("template")
 				.short('t')
-				.help("Template: discord | next | typescript")
-				.required(false),
+				.help("Template: discord | next | typescript (or one from config)")
+				.required(false)
+				.add(ArgValueCandidates::new(template_candidates)),
 		)
 		.arg(
 			Arg::new("root")
 				.long("root")
 				.help(
-					"Base path for projects (defaults to %EAGLE_CREATE_ROOT% or %USERPROFILE%\\Development\\.YY)",
+					"Base path for projects (defaults to $EAGLE_CREATE_ROOT, config create.root or ~/Development/.YY)",
 				)
 				.required(false),
 		)
 }
 
-fn run(matches: &ArgMatches, _: &Context) -> anyhow::Result<()> {
-	if which::which("git").is_err() {
-		anyhow::bail!("git not found in PATH");
-	}
-	if which::which("bun").is_err() {
-		anyhow::bail!("bun not found in PATH");
-	}
-
+fn run(matches: &ArgMatches, ctx: &Context) -> anyhow::Result<()> {
 	let name = match matches.get_one::<String>("name") {
 		Some(v) => v.clone(),
 		None => prompt_name()?,
 	};
 	if name.trim().is_empty() {
-		anyhow::bail!("Project name must not be empty");
+		return Err(error::usage("Project name must not be empty"));
 	}
 
+	let templates = templates(&ctx.config)?;
 	let template = match matches.get_one::<String>("template") {
 		Some(v) => v.to_string(),
-		None => select_template()?,
+		None => select_template(&templates)?,
 	};
 
 	let template = template.to_lowercase();
 
 	let year = current_two_digit_year()?;
-	let base_root = resolve_base_root(matches, &year)?;
-
-	let (target_subdir, repo_url) = match template.as_str() {
-		"discord" => (
-			"discord",
-			"https://github.com/meowlounge/discord-template.git",
-		),
-		"next" => (
-			"frontend",
-			"https://github.com/meowlounge/next-template.git",
-		),
-		"typescript" => (
-			"typescript",
-			"https://github.com/meowlounge/typescript-template.git",
-		),
-		_ => anyhow::bail!(
-			"unknown template '{template}' — valid options: discord, next, typescript"
-		),
+	let base_root = resolve_base_root(matches, ctx, &year)?;
+
+	let Some(template) = templates.iter().find(|t| t.name == template) else {
+		let valid = templates
+			.iter()
+			.map(|t| t.name.as_str())
+			.collect::<Vec<_>>()
+			.join(", ");
+		return Err(error::unknown_value(
+			format!("unknown template '{template}' — valid options: {valid}"),
+			&template,
+			templates.iter().map(|t| t.name.as_str()),
+		));
 	};
-	let target_root = base_root.join(target_subdir);
+	let repo_url = template.repo.as_str();
+	let target_root = base_root.join(&template.dir);
 	ui::muted(&format!("Target root: {}", target_root.display()));
 
-	std::fs::create_dir_all(&target_root)?;
+	ctx.exec.create_dir_all(&target_root)?;
 
 	let project_path = target_root.join(&name);
 	if project_path.exists() {
-		anyhow::bail!("Project already exists: {}", project_path.display());
+		return Err(error::usage(format!(
+			"Project already exists: {}",
+			project_path.display()
+		)));
 	}
 	ui::info(&format!("Cloning template: {repo_url}"));
+	// Removes a half-cloned project on failure or Ctrl-C. Once the clone is
+	// done the project is kept, even if `bun update` fails or is cancelled.
+	let mut guard =
+		(!ctx.exec.dry_run()).then(|| DirGuard::new(project_path.clone()));
 
 	let project_path_s = project_path.to_string_lossy();
-	let status =
-		util::run_inherit("git", &["clone", repo_url, &project_path_s])?;
+	let status = timings::phase("clone template", || {
+		ctx.exec
+			.run_inherit("git", &["clone", repo_url, &project_path_s], None)
+	})?;
 	if !status.success() {
-		anyhow::bail!("git clone failed");
+		return Err(error::process_failed("git clone", status));
 	}
 
+	// In a dry run nothing was cloned, but the removal is still planned.
 	let git_dir = project_path.join(".git");
-	if git_dir.exists() {
-		std::fs::remove_dir_all(git_dir)?;
+	if git_dir.exists() || ctx.exec.dry_run() {
+		ctx.exec.remove_dir_all(&git_dir)?;
+	}
+	if let Some(mut guard) = guard.take() {
+		guard.commit();
 	}
 
 	ui::info("Updating dependencies with Bun...");
-	let status = util::run_inherit_with_dir(
-		"bun",
-		&["update", "--latest"],
-		&project_path,
-	)?;
+	let status = timings::phase("update dependencies", || {
+		ctx.exec.run_inherit(
+			"bun",
+			&["update", "--latest"],
+			Some(&project_path),
+		)
+	})?;
 	if !status.success() {
-		anyhow::bail!("bun update failed");
+		return Err(error::process_failed("bun update", status));
 	}
 
 	ui::success(&format!("Project created: {}", project_path.display()));
+	ui::result(serde_json::json!({
+		"path": project_path,
+		"name": name,
+		"template": template.name,
+	}));
 	Ok(())
 }
 
+/// Returns the built-in templates merged with `[templates.<name>]` tables
+/// from the config. Config entries override built-ins of the same name.
+#[doc(hidden)]
+pub fn templates(config: &Config) -> anyhow::Result<Vec<Template>> {
+	let mut out: Vec<Template> = BUILTIN_TEMPLATES
+		.iter()
+		.map(|(name, dir, repo)| Template {
+			name: (*name).to_string(),
+			dir: (*dir).to_string(),
+			repo: (*repo).to_string(),
+		})
+		.collect();
+
+	let Some(custom) = config.get("templates") else {
+		return Ok(out);
+	};
+	let custom = custom.as_table().ok_or_else(|| {
+		anyhow::anyhow!("Config key 'templates' must be a table")
+	})?;
+
+	for key in custom.keys() {
+		let repo = config
+			.string(&format!("templates.{key}.repo"))?
+			.ok_or_else(|| {
+				anyhow::anyhow!("Config template '{key}' is missing 'repo'")
+			})?;
+		// Template names match case-insensitively, so store them lowercased.
+		let name = key.to_lowercase();
+		let dir = config
+			.string(&format!("templates.{key}.dir"))?
+			.unwrap_or_else(|| name.clone());
+
+		let template = Template { name, dir, repo };
+		match out.iter_mut().find(|t| t.name == template.name) {
+			Some(existing) => *existing = template,
+			None => out.push(template),
+		}
+	}
+
+	Ok(out)
+}
+
+fn template_candidates() -> Vec<CompletionCandidate> {
+	let config = Config::load_default();
+	templates(&config)
+		.unwrap_or_default()
+		.into_iter()
+		.map(|t| CompletionCandidate::new(t.name).help(Some(t.repo.into())))
+		.collect()
+}
+
 fn resolve_base_root(
 	matches: &ArgMatches,
+	ctx: &Context,
 	year: &str,
 ) -> anyhow::Result<PathBuf> {
 	if let Some(value) = matches.get_one::<String>("root") {
-		return Ok(PathBuf::from(value));
+		return Ok(config::expand_tilde(value));
 	}
 
-	if let Ok(from_env) = std::env::var("EAGLE_CREATE_ROOT") {
-		let value = from_env.trim();
-		if value.is_empty() {
-			anyhow::bail!("EAGLE_CREATE_ROOT is set but empty");
-		}
-		return Ok(PathBuf::from(value));
-	}
+	configured_base_root(&ctx.config, &ctx.paths, year)
+}
 
-	let home = directories::UserDirs::new()
-		.map(|u| u.home_dir().to_path_buf())
-		.ok_or_else(|| {
-			anyhow::anyhow!("Could not resolve user home directory")
-		})?;
+/// Resolves the base root without a `--root` flag: config (or env), then
+/// `projects/.YY` in `EAGLE_HOME` or `~/Development/.YY`.
+pub(crate) fn configured_base_root(
+	config: &Config,
+	paths: &Paths,
+	year: &str,
+) -> anyhow::Result<PathBuf> {
+	if let Some(value) = config.path_value("create.root")? {
+		return Ok(value);
+	}
 
-	Ok(home.join("Development").join(format!(".{year}")))
+	paths.projects_root(year)
 }
 
 fn prompt_name() -> anyhow::Result<String> {
-	Input::<String>::new()
+	ui::require_interactive("a project name", "--name <NAME>")?;
+	let theme = ui::theme();
+	Input::<String>::with_theme(theme.as_ref())
 		.with_prompt("Enter project name")
 		.interact_text()
-		.map_err(|err| anyhow::anyhow!("Failed to read project name: {err}"))
+		.context("Failed to read project name")
 }
 
-fn select_template() -> anyhow::Result<String> {
-	let options = ["discord", "next", "typescript"];
-	let selection = Select::new()
+fn select_template(templates: &[Template]) -> anyhow::Result<String> {
+	ui::require_interactive("a template", "--template <TEMPLATE>")?;
+	let options: Vec<&str> =
+		templates.iter().map(|t| t.name.as_str()).collect();
+	let theme = ui::theme();
+	let selection = Select::with_theme(theme.as_ref())
 		.with_prompt("Choose a template")
 		.items(&options)
 		.default(0)
 		.interact()
-		.map_err(|err| anyhow::anyhow!("Failed to select template: {err}"))?;
+		.context("Failed to select template")?;
 	let chosen = options.get(selection).ok_or_else(|| {
 		anyhow::anyhow!("dialoguer returned out-of-bounds index {selection}")
 	})?;
 	Ok((*chosen).to_string())
 }
 
-fn current_two_digit_year() -> anyhow::Result<String> {
+pub(crate) fn current_two_digit_year() -> anyhow::Result<String> {
 	let now = time::OffsetDateTime::now_local()
 		.unwrap_or_else(|_| time::OffsetDateTime::now_utc());
 	let year = now.year() % 100;
@@ -174,5 +275,19 @@ inventory::submit! {
 		name: "create",
 		command: build,
 		run,
+		examples: &[
+			Example {
+				command: "eagle create --name bot --template discord",
+				about: "Clone the Discord template into <root>/discord/bot",
+			},
+			Example {
+				command: "eagle create -n site -t next --root ~/Projects",
+				about: "Use another base folder for this project",
+			},
+		],
+		category: Category::Workflows,
+		platforms: &[],
+		tools: &[GIT, BUN],
+		stability: Stability::Stable,
 	}
 }
@@ -0,0 +1,264 @@
+use std::path::PathBuf;
+
+use clap::{Arg, ArgMatches, Command};
+use clap_mangen::roff::{Roff, roman};
+
+use crate::commands::{Category, CommandSpec, Example, Stability};
+use crate::context::Context;
+use crate::ui;
+
+fn build() -> Command {
+	Command::new("docs")
+		.about("Generate man pages or a Markdown reference from the CLI")
+		.arg(
+			Arg::new("format")
+				.long("format")
+				.help("Output format")
+				.value_parser(["man", "markdown"])
+				.default_value("markdown"),
+		)
+		.arg(
+			Arg::new("out")
+				.long("out")
+				.value_name("DIR")
+				.help("Directory to write the pages to")
+				.value_parser(clap::value_parser!(PathBuf))
+				.required(true),
+		)
+}
+
+fn run(matches: &ArgMatches, ctx: &Context) -> anyhow::Result<()> {
+	let out_dir = matches
+		.get_one::<PathBuf>("out")
+		.ok_or_else(|| anyhow::anyhow!("missing --out"))?;
+	let format = matches
+		.get_one::<String>("format")
+		.map(String::as_str)
+		.unwrap_or("markdown");
+
+	let pages = match format {
+		"man" => render_man(crate::cli::build_cli())?,
+		_ => render_markdown(crate::cli::build_cli()),
+	};
+
+	ctx.exec.create_dir_all(out_dir)?;
+	for (file_name, content) in &pages {
+		ctx.exec
+			.write(&out_dir.join(file_name), content.as_bytes())?;
+	}
+
+	ui::success(&format!(
+		"Wrote {} {format} pages to {}",
+		pages.len(),
+		out_dir.display()
+	));
+	ui::result(serde_json::json!({
+		"format": format,
+		"out": out_dir,
+		"files": pages.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+	}));
+	Ok(())
+}
+
+/// Returns every visible command in the tree, root first, with bin names
+/// (`eagle minecraft create`) filled in.
+fn walk(mut root: Command) -> Vec<Command> {
+	root.build();
+
+	let mut out = Vec::new();
+	let mut stack = vec![root];
+	while let Some(cmd) = stack.pop() {
+		let mut subs: Vec<Command> = cmd
+			.get_subcommands()
+			.filter(|s| !s.is_hide_set())
+			.cloned()
+			.collect();
+		subs.reverse();
+		stack.extend(subs);
+		out.push(cmd);
+	}
+	out
+}
+
+fn page_name(cmd: &Command) -> String {
+	cmd.get_display_name()
+		.unwrap_or_else(|| cmd.get_name())
+		.to_string()
+}
+
+fn full_name(cmd: &Command) -> String {
+	cmd.get_bin_name()
+		.unwrap_or_else(|| cmd.get_name())
+		.to_string()
+}
+
+/// Renders one roff page per command, named `eagle-<path>.1`.
+#[doc(hidden)]
+pub fn render_man(root: Command) -> anyhow::Result<Vec<(String, String)>> {
+	let mut pages = Vec::new();
+
+	for cmd in walk(root) {
+		let aliases: Vec<&str> = cmd.get_all_aliases().collect();
+		let man = clap_mangen::Man::new(cmd.clone());
+
+		let mut buf = Vec::new();
+		man.render(&mut buf)?;
+
+		if !aliases.is_empty() {
+			let mut roff = Roff::default();
+			roff.control("SH", ["ALIASES"]);
+			roff.text([roman(aliases.join(", "))]);
+			roff.to_writer(&mut buf)?;
+		}
+
+		pages.push((man.get_filename(), String::from_utf8(buf)?));
+	}
+
+	Ok(pages)
+}
+
+/// Renders one Markdown page per command, named `eagle-<path>.md`.
+#[doc(hidden)]
+pub fn render_markdown(root: Command) -> Vec<(String, String)> {
+	walk(root)
+		.into_iter()
+		.map(|mut cmd| {
+			let file_name = format!("{}.md", page_name(&cmd));
+			(file_name, markdown_page(&mut cmd))
+		})
+		.collect()
+}
+
+fn markdown_page(cmd: &mut Command) -> String {
+	let mut out = format!("# {}\n\n", full_name(cmd));
+
+	if let Some(about) = cmd.get_long_about().or_else(|| cmd.get_about()) {
+		out.push_str(&format!("{about}\n\n"));
+	}
+
+	let aliases: Vec<String> =
+		cmd.get_all_aliases().map(|a| format!("`{a}`")).collect();
+	if !aliases.is_empty() {
+		out.push_str(&format!("Aliases: {}\n\n", aliases.join(", ")));
+	}
+
+	let usage = cmd.render_usage().to_string();
+	let usage = usage.trim_start_matches("Usage:").trim();
+	out.push_str(&format!("## Usage\n\n```text\n{usage}\n```\n\n"));
+
+	let subs: Vec<&Command> =
+		cmd.get_subcommands().filter(|s| !s.is_hide_set()).collect();
+	if !subs.is_empty() {
+		out.push_str("## Commands\n\n| Command | Aliases | Description |\n");
+		out.push_str("|---|---|---|\n");
+		for sub in subs {
+			let aliases: Vec<&str> = sub.get_all_aliases().collect();
+			out.push_str(&format!(
+				"| [`{}`]({}.md) | {} | {} |\n",
+				full_name(sub),
+				page_name(sub),
+				cell(&aliases.join(", ")),
+				cell(
+					&sub.get_about().map(|a| a.to_string()).unwrap_or_default()
+				),
+			));
+		}
+		out.push('\n');
+	}
+
+	let args: Vec<&Arg> = cmd
+		.get_arguments()
+		.filter(|a| !a.is_hide_set() && a.get_id() != "help")
+		.collect();
+	if !args.is_empty() {
+		out.push_str("## Arguments\n\n| Argument | Description | Default |\n");
+		out.push_str("|---|---|---|\n");
+		for arg in args {
+			out.push_str(&format!(
+				"| `{}` | {} | {} |\n",
+				cell(&arg_label(arg)),
+				cell(&arg_description(arg)),
+				cell(&arg_default(arg)),
+			));
+		}
+		out.push('\n');
+	}
+
+	out
+}
+
+/// `text` made safe for a Markdown table cell: one line, with `|` escaped
+/// so it does not start a new column.
+fn cell(text: &str) -> String {
+	text.replace('\n', " ").replace('|', "\\|")
+}
+
+fn arg_label(arg: &Arg) -> String {
+	let value = arg
+		.get_value_names()
+		.and_then(|names| names.first())
+		.map(|n| n.to_string())
+		.unwrap_or_else(|| arg.get_id().to_string());
+	let takes_value = arg.get_num_args().is_some_and(|n| n.takes_values());
+
+	let flag = match (arg.get_short(), arg.get_long()) {
+		(Some(s), Some(l)) => format!("-{s}, --{l}"),
+		(Some(s), None) => format!("-{s}"),
+		(None, Some(l)) => format!("--{l}"),
+		(None, None) => return format!("<{value}>"),
+	};
+
+	if takes_value {
+		format!("{flag} <{value}>")
+	} else {
+		flag
+	}
+}
+
+fn arg_description(arg: &Arg) -> String {
+	let mut text = arg
+		.get_long_help()
+		.or_else(|| arg.get_help())
+		.map(|h| h.to_string())
+		.unwrap_or_default();
+
+	let values: Vec<String> = arg
+		.get_possible_values()
+		.iter()
+		.filter(|v| !v.is_hide_set())
+		.map(|v| format!("`{}`", v.get_name()))
+		.collect();
+	if !values.is_empty() {
+		text.push_str(&format!(" (one of: {})", values.join(", ")));
+	}
+
+	text
+}
+
+fn arg_default(arg: &Arg) -> String {
+	if !arg.get_num_args().is_some_and(|n| n.takes_values()) {
+		return String::new();
+	}
+
+	arg.get_default_values()
+		.iter()
+		.map(|v| format!("`{}`", v.to_string_lossy()))
+		.collect::<Vec<_>>()
+		.join(", ")
+}
+
+inventory::submit! {
+	CommandSpec {
+		name: "docs",
+		command: build,
+		run,
+		examples: &[Example {
+			command: "eagle docs --format man --out target/man",
+			about: "Write man pages for every command",
+		}],
+		category: Category::Help,
+		platforms: &[],
+		tools: &[],
+		stability: Stability::Hidden,
+	}
+}
@@ -0,0 +1,258 @@
+use std::path::Path;
+
+use clap::{ArgMatches, Command};
+use crossterm::style::Stylize;
+use serde::Serialize;
+
+use crate::commands::create;
+use crate::commands::minecraft::fs;
+use crate::commands::update::RELEASES_PATH;
+use crate::commands::{Category, CommandSpec, Example, Stability};
+use crate::context::Context;
+use crate::error::{self, Error};
+use crate::net::{self, Endpoints};
+use crate::process::ProcessRunner;
+use crate::ui;
+
+/// Oldest Java that runs current Minecraft servers (1.20.5+).
+const MIN_JAVA_MAJOR: u32 = 21;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+	Pass,
+	Warn,
+	Fail,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Check {
+	pub name: String,
+	pub status: Status,
+	pub detail: String,
+}
+
+impl Check {
+	fn new(name: &str, status: Status, detail: impl Into<String>) -> Self {
+		Self {
+			name: name.to_string(),
+			status,
+			detail: detail.into(),
+		}
+	}
+}
+
+fn build() -> Command {
+	Command::new("doctor")
+		.about("Check tools, folders and services the other commands need")
+}
+
+fn run(_: &ArgMatches, ctx: &Context) -> anyhow::Result<()> {
+	let processes = ctx.processes.as_ref();
+	let mut checks = vec![
+		check_tool(processes, "git", Status::Fail),
+		check_tool(processes, "bun", Status::Fail),
+		check_java(processes),
+		check_tool(processes, "brew", Status::Warn),
+		check_dir("exe dir", Ok(ctx.exe_dir.clone())),
+	];
+
+	let year = create::current_two_digit_year()?;
+	checks.push(check_dir(
+		"create root",
+		create::configured_base_root(&ctx.config, &ctx.paths, &year),
+	));
+	checks.push(check_dir(
+		"servers root",
+		fs::servers_root(&ctx.config, &ctx.paths),
+	));
+
+	let endpoints = Endpoints::from_config(&ctx.config)?;
+	checks.push(check_endpoint(
+		"github api",
+		&format!("{}{RELEASES_PATH}", endpoints.github_api),
+	));
+	checks.push(check_endpoint(
+		"paper api",
+		&format!("{}/v3/projects/paper", endpoints.paper_api),
+	));
+	checks.push(check_endpoint(
+		"fabric meta",
+		&format!("{}/v2/versions/loader", endpoints.fabric_meta),
+	));
+
+	if !ui::json_enabled() {
+		print_table(&checks);
+	}
+	ui::result(serde_json::json!({ "checks": checks }));
+
+	let count = |status| checks.iter().filter(|c| c.status == status).count();
+	let (failed, warned) = (count(Status::Fail), count(Status::Warn));
+	if failed > 0 {
+		return Err(Error::new(
+			error::Category::General,
+			format!("{failed} of {} checks failed", checks.len()),
+		)
+		.into());
+	}
+
+	if warned > 0 {
+		ui::warning(&format!("All checks passed, {warned} with warnings"));
+	} else {
+		ui::success("All checks passed");
+	}
+	Ok(())
+}
+
+fn check_tool(
+	processes: &dyn ProcessRunner,
+	name: &str,
+	missing: Status,
+) -> Check {
+	match processes.which(name) {
+		Some(path) => {
+			Check::new(name, Status::Pass, path.display().to_string())
+		}
+		None => Check::new(name, missing, "not found in PATH"),
+	}
+}
+
+fn check_java(processes: &dyn ProcessRunner) -> Check {
+	let Some(path) = processes.which("java") else {
+		return Check::new("java", Status::Fail, "not found in PATH");
+	};
+
+	let output =
+		processes.run_capture_combined(&path.to_string_lossy(), &["-version"]);
+	match output.ok().as_deref().and_then(parse_java_major) {
+		Some(major) if major >= MIN_JAVA_MAJOR => Check::new(
+			"java",
+			Status::Pass,
+			format!("Java {major} ({})", path.display()),
+		),
+		Some(major) => Check::new(
+			"java",
+			Status::Warn,
+			format!(
+				"Java {major} found; Minecraft servers need Java {MIN_JAVA_MAJOR}+"
+			),
+		),
+		None => Check::new(
+			"java",
+			Status::Warn,
+			format!("could not read the version of {}", path.display()),
+		),
+	}
+}
+
+/// Extracts the major version from `java -version` output, mapping legacy
+/// `1.8.0_x` style versions to 8.
+#[doc(hidden)]
+pub fn parse_java_major(output: &str) -> Option<u32> {
+	let start = output.find('"')? + 1;
+	let rest = output.get(start..)?;
+	let version = rest.get(..rest.find('"')?)?;
+
+	let mut parts = version.split(['.', '_', '-', '+']);
+	let first: u32 = parts.next()?.parse().ok()?;
+	if first == 1 {
+		parts.next()?.parse().ok()
+	} else {
+		Some(first)
+	}
+}
+
+fn check_dir(name: &str, dir: anyhow::Result<std::path::PathBuf>) -> Check {
+	let dir = match dir {
+		Ok(dir) => dir,
+		Err(err) => return Check::new(name, Status::Fail, err.to_string()),
+	};
+
+	// A folder that does not exist yet is fine as long as the closest
+	// existing parent lets us create it.
+	let Some(existing) = dir.ancestors().find(|p| p.is_dir()) else {
+		return Check::new(
+			name,
+			Status::Fail,
+			format!("{} does not resolve", dir.display()),
+		);
+	};
+
+	match probe_writable(existing) {
+		Ok(()) if existing == dir => {
+			Check::new(name, Status::Pass, dir.display().to_string())
+		}
+		Ok(()) => Check::new(
+			name,
+			Status::Pass,
+			format!("{} (will be created)", dir.display()),
+		),
+		Err(err) => Check::new(
+			name,
+			Status::Fail,
+			format!("{} is not writable: {err}", existing.display()),
+		),
+	}
+}
+
+fn probe_writable(dir: &Path) -> std::io::Result<()> {
+	let probe = dir.join(format!(".eagle-doctor-{}", std::process::id()));
+	std::fs::OpenOptions::new()
+		.write(true)
+		.create_new(true)
+		.open(&probe)?;
+	std::fs::remove_file(&probe)
+}
+
+fn check_endpoint(name: &str, url: &str) -> Check {
+	match net::probe(url) {
+		Ok(code) if (200..300).contains(&code) => {
+			Check::new(name, Status::Pass, format!("{url} ({code})"))
+		}
+		// Reachable, but GitHub is rate limiting this address.
+		Ok(code @ (403 | 429)) => {
+			Check::new(name, Status::Warn, format!("{url} (HTTP {code})"))
+		}
+		Ok(code) => {
+			Check::new(name, Status::Fail, format!("{url} (HTTP {code})"))
+		}
+		Err(err) => Check::new(name, Status::Fail, format!("{err:#}")),
+	}
+}
+
+fn print_table(checks: &[Check]) {
+	let width = checks.iter().map(|c| c.name.len()).max().unwrap_or(0);
+	for check in checks {
+		let label = match check.status {
+			Status::Pass => "pass",
+			Status::Warn => "warn",
+			Status::Fail => "fail",
+		};
+		let label = if ui::stdout_colors() {
+			match check.status {
+				Status::Pass => label.green().to_string(),
+				Status::Warn => label.yellow().to_string(),
+				Status::Fail => label.red().bold().to_string(),
+			}
+		} else {
+			label.to_string()
+		};
+		println!("{label}  {:<width$}  {}", check.name, check.detail);
+	}
+}
+
+inventory::submit! {
+	CommandSpec {
+		name: "doctor",
+		command: build,
+		run,
+		examples: &[Example {
+			command: "eagle doctor --json",
+			about: "Run every check and print the results as JSON",
+		}],
+		category: Category::Diagnostics,
+		platforms: &[],
+		tools: &[],
+		stability: Stability::Stable,
+	}
+}
@@ -2,10 +2,12 @@ use std::path::{Path, PathBuf};
 
 use clap::{Arg, ArgMatches, Command};
 
-use crate::commands::CommandSpec;
+use crate::commands::{BUN, Category, CommandSpec, Example, GIT, Stability};
 use crate::context::Context;
+use crate::error;
+use crate::exec::Executor;
+use crate::timings;
 use crate::ui;
-use crate::util;
 
 fn build() -> Command {
 	Command::new("eaglecord")
@@ -22,106 +24,132 @@ fn build() -> Command {
 
 fn build_dev() -> Command {
 	Command::new("eaglecord-dev")
-		.hide(true)
 		.about("EagleCord dev mode (reinstall)")
 		.alias("e:dev")
 }
 
-fn run(matches: &ArgMatches, _: &Context) -> anyhow::Result<()> {
+fn run(matches: &ArgMatches, ctx: &Context) -> anyhow::Result<()> {
 	let reinstall = matches.get_flag("reinstall");
-	run_impl(reinstall)
+	run_impl(reinstall, ctx)
 }
 
-fn run_dev(_: &ArgMatches, _: &Context) -> anyhow::Result<()> {
-	run_impl(true)
+fn run_dev(_: &ArgMatches, ctx: &Context) -> anyhow::Result<()> {
+	run_impl(true, ctx)
 }
 
-fn run_impl(reinstall: bool) -> anyhow::Result<()> {
-	ensure_tool("git")?;
-	let bun = ensure_bun()?;
+fn run_impl(reinstall: bool, ctx: &Context) -> anyhow::Result<()> {
+	let exec = ctx.exec.as_ref();
+	let bun = timings::phase("ensure bun", || ensure_bun(ctx))?;
 
 	let repo_url = "https://github.com/prodbyeagle/cord";
 	let repo_name = "Vencord";
 
-	// On macOS the conventional location for app data is
-	// ~/Library/Application Support/
-	let data_dir = directories::BaseDirs::new()
-		.map(|b| b.data_dir().to_path_buf())
-		.ok_or_else(|| anyhow::anyhow!("Could not resolve data directory"))?;
-	let temp_root = data_dir.join("EagleCord");
+	let temp_root = eaglecord_root(ctx)?;
 	let clone_dir = temp_root.join(repo_name);
 
-	std::fs::create_dir_all(&temp_root)?;
+	exec.create_dir_all(&temp_root)?;
 
-	if reinstall && clone_dir.exists() {
+	// A dry run keeps the clone, so only a real removal skips the update.
+	let removed = reinstall && clone_dir.exists();
+	if removed {
 		ui::warning(&format!("Reinstall: removing {}", clone_dir.display()));
-		std::fs::remove_dir_all(&clone_dir)?;
+		exec.remove_dir_all(&clone_dir)?;
 	}
 
-	if clone_dir.exists() {
-		ensure_repo_clean(&clone_dir)?;
-		update_repo(repo_url, &clone_dir)?;
+	if clone_dir.exists() && !(removed && exec.dry_run()) {
+		timings::phase("update", || {
+			ensure_repo_clean(exec, &clone_dir)?;
+			update_repo(exec, repo_url, &clone_dir)
+		})?;
 	} else {
 		ui::info("Cloning repo...");
 		let clone_dir_s = clone_dir.to_string_lossy();
-		let status =
-			util::run_inherit("git", &["clone", repo_url, &clone_dir_s])?;
+		let status = timings::phase("clone", || {
+			exec.run_inherit("git", &["clone", repo_url, &clone_dir_s], None)
+		})?;
 		if !status.success() {
-			anyhow::bail!("git clone failed");
+			return Err(error::process_failed("git clone", status));
 		}
 	}
 
 	let dist = clone_dir.join("dist");
 	if dist.exists() {
-		std::fs::remove_dir_all(dist)?;
+		exec.remove_dir_all(&dist)?;
 	}
 
 	let discord_types = clone_dir.join("packages/discord-types");
 	if discord_types.exists() {
 		ui::info("Linking @vencord/discord-types...");
-		run_bun_in_dir(&bun, &discord_types, &["link"])?;
+		timings::phase("link", || {
+			run_bun_in_dir(exec, &bun, &discord_types, &["link"])
+		})?;
 	}
 
 	ui::info("Installing dependencies...");
-	run_bun_in_dir(&bun, &clone_dir, &["install"])?;
+	timings::phase("install", || {
+		run_bun_in_dir(exec, &bun, &clone_dir, &["install"])
+	})?;
 
 	ui::info("Building...");
-	run_bun_in_dir(&bun, &clone_dir, &["run", "build"])?;
+	timings::phase("build", || {
+		run_bun_in_dir(exec, &bun, &clone_dir, &["run", "build"])
+	})?;
 
 	ui::info("Injecting...");
-	run_bun_in_dir(&bun, &clone_dir, &["inject"])?;
+	timings::phase("inject", || {
+		run_bun_in_dir(exec, &bun, &clone_dir, &["inject"])
+	})?;
 
 	ui::success("EagleCord complete.");
+	ui::result(serde_json::json!({
+		"path": clone_dir,
+		"reinstalled": reinstall,
+	}));
 	Ok(())
 }
 
-fn ensure_tool(name: &str) -> anyhow::Result<()> {
-	which::which(name)
-		.map(|_| ())
-		.map_err(|_| anyhow::anyhow!("Required tool not found: {name}"))
+/// Returns `eaglecord.dir` from env/config, or the default from
+/// [`Paths::eaglecord_dir`](crate::paths::Paths::eaglecord_dir).
+fn eaglecord_root(ctx: &Context) -> anyhow::Result<PathBuf> {
+	if let Some(dir) = ctx.config.path_value("eaglecord.dir")? {
+		return Ok(dir);
+	}
+
+	ctx.paths.eaglecord_dir()
 }
 
-fn ensure_bun() -> anyhow::Result<PathBuf> {
-	if let Ok(path) = which::which("bun") {
+fn ensure_bun(ctx: &Context) -> anyhow::Result<PathBuf> {
+	let processes = ctx.processes.as_ref();
+	if let Some(path) = processes.which("bun") {
 		return Ok(path);
 	}
 
-	if which::which("brew").is_err() {
-		anyhow::bail!(
-			"bun not found and Homebrew is unavailable. Install Bun manually: https://bun.sh"
-		);
+	if processes.which("brew").is_none() {
+		return Err(error::Error::new(
+			error::Category::MissingTool,
+			"bun not found and Homebrew is unavailable",
+		)
+		.with_hint(BUN.hint)
+		.into());
 	}
 
 	ui::info("Bun not found. Installing with Homebrew...");
 	let install_status =
-		util::run_inherit("brew", &["install", "oven-sh/bun/bun"])?;
+		ctx.exec
+			.run_inherit("brew", &["install", "oven-sh/bun/bun"], None)?;
 	if !install_status.success() {
-		anyhow::bail!("Bun install failed (brew install: {install_status})");
+		return Err(error::process_failed(
+			"brew install oven-sh/bun/bun",
+			install_status,
+		));
 	}
 
-	if let Ok(path) = which::which("bun") {
+	if let Some(path) = processes.which("bun") {
 		return Ok(path);
 	}
+	if ctx.exec.dry_run() {
+		return Ok(PathBuf::from("bun"));
+	}
 
 	// Fallback: bun installs itself to ~/.bun/bin/bun
 	let home = directories::UserDirs::new()
@@ -133,13 +161,13 @@ fn ensure_bun() -> anyhow::Result<PathBuf> {
 		return Ok(fallback);
 	}
 
-	anyhow::bail!("bun still not found after install")
+	Err(error::missing_tool(BUN.name, BUN.hint))
 }
 
-fn ensure_repo_clean(dir: &Path) -> anyhow::Result<()> {
+fn ensure_repo_clean(exec: &dyn Executor, dir: &Path) -> anyhow::Result<()> {
 	let dir_s = dir.to_string_lossy().to_string();
 	let dirty =
-		util::run_capture("git", &["-C", &dir_s, "status", "--porcelain"])?;
+		exec.run_capture("git", &["-C", &dir_s, "status", "--porcelain"])?;
 	if dirty.trim().is_empty() {
 		return Ok(());
 	}
@@ -150,10 +178,15 @@ fn ensure_repo_clean(dir: &Path) -> anyhow::Result<()> {
 	);
 }
 
-fn update_repo(repo_url: &str, dir: &Path) -> anyhow::Result<()> {
+fn update_repo(
+	exec: &dyn Executor,
+	repo_url: &str,
+	dir: &Path,
+) -> anyhow::Result<()> {
 	let dir_s = dir.to_string_lossy().to_string();
-	let local = util::run_capture("git", &["-C", &dir_s, "rev-parse", "HEAD"])?;
-	let remote = util::run_capture("git", &["ls-remote", repo_url, "HEAD"])?;
+	let local =
+		exec.run_capture("git", &["-C", &dir_s, "rev-parse", "HEAD"])?;
+	let remote = exec.run_capture("git", &["ls-remote", repo_url, "HEAD"])?;
 
 	let remote_hash = remote.split('\t').next().unwrap_or("").trim();
 	if local.trim() == remote_hash {
@@ -162,31 +195,34 @@ fn update_repo(repo_url: &str, dir: &Path) -> anyhow::Result<()> {
 	}
 
 	ui::info("Updating repo...");
-	let status = util::run_inherit("git", &["-C", &dir_s, "fetch", "origin"])?;
+	let status =
+		exec.run_inherit("git", &["-C", &dir_s, "fetch", "origin"], None)?;
 	if !status.success() {
-		anyhow::bail!("git fetch failed");
+		return Err(error::process_failed("git fetch", status));
 	}
 
 	let status =
-		util::run_inherit("git", &["-C", &dir_s, "pull", "--ff-only"])?;
+		exec.run_inherit("git", &["-C", &dir_s, "pull", "--ff-only"], None)?;
 	if !status.success() {
-		anyhow::bail!("git pull --ff-only failed");
+		return Err(error::process_failed("git pull --ff-only", status));
 	}
 
 	Ok(())
 }
 
-fn run_bun_in_dir(bun: &Path, dir: &Path, args: &[&str]) -> anyhow::Result<()> {
-	let status = std::process::Command::new(bun)
-		.args(args)
-		.current_dir(dir)
-		.stdin(std::process::Stdio::inherit())
-		.stdout(std::process::Stdio::inherit())
-		.stderr(std::process::Stdio::inherit())
-		.status()?;
+fn run_bun_in_dir(
+	exec: &dyn Executor,
+	bun: &Path,
+	dir: &Path,
+	args: &[&str],
+) -> anyhow::Result<()> {
+	let status = exec.run_inherit(&bun.to_string_lossy(), args, Some(dir))?;
 
 	if !status.success() {
-		anyhow::bail!("bun {:?} failed: {status}", args);
+		return Err(error::process_failed(
+			&format!("bun {}", args.join(" ")),
+			status,
+		));
 	}
 
 	Ok(())
@@ -197,6 +233,14 @@ inventory::submit! {
 		name: "eaglecord",
 		command: build,
 		run,
+		examples: &[Example {
+			command: "eagle eaglecord --reinstall",
+			about: "Delete the local clone and install from scratch",
+		}],
+		category: Category::Workflows,
+		platforms: &[],
+		tools: &[GIT],
+		stability: Stability::Stable,
 	}
 }
 
@@ -205,5 +249,10 @@ inventory::submit! {
 		name: "eaglecord-dev",
 		command: build_dev,
 		run: run_dev,
+		examples: &[],
+		category: Category::Workflows,
+		platforms: &[],
+		tools: &[GIT],
+		stability: Stability::Hidden,
 	}
 }
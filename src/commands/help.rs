@@ -1,48 +1,156 @@
 use clap::{Arg, ArgMatches, Command};
 
-use crate::commands::CommandSpec;
+use crate::alias::Aliases;
+use crate::commands::{Category, CommandSpec, Example, Stability};
 use crate::context::Context;
+use crate::util;
 
 fn build() -> Command {
 	Command::new("help").about("Show help").alias("h").arg(
 		Arg::new("command")
-			.help("Command to show help for")
+			.help("Command to show help for, e.g. `minecraft create`")
+			.num_args(1..)
 			.required(false),
 	)
 }
 
-fn run(matches: &ArgMatches, _: &Context) -> anyhow::Result<()> {
-	let mut cli = crate::cli::build_cli();
+fn run(matches: &ArgMatches, ctx: &Context) -> anyhow::Result<()> {
+	let mut cli = crate::cli::with_tool_marks(crate::cli::build_cli());
+	let aliases = Aliases::load(&ctx.config, &cli);
 
-	if let Some(target) = matches.get_one::<String>("command") {
-		let target = target.as_str();
+	if let Some(words) = matches.get_many::<String>("command") {
+		let mut path: Vec<String> = words.cloned().collect();
 
-		if let Some(mut sub) = find_subcommand(&mut cli, target) {
-			println!("{}", sub.render_long_help());
-			return Ok(());
+		if let Some(alias) = path.first().and_then(|w| aliases.find(w)) {
+			println!(
+				"'{}' is an alias for 'eagle {}'\n",
+				alias.name,
+				alias.expansion.join(" ")
+			);
+			let prefix = walk(&cli, &alias.expansion).len().max(1);
+			let expansion = alias.expansion.iter().take(prefix).cloned();
+			path.splice(..1, expansion);
 		}
 
-		anyhow::bail!("Unknown command: {target}");
+		// Sets bin names, so usage reads `eagle minecraft create`.
+		cli.build();
+		return show(&cli, &path);
+	}
+
+	let plugins: Vec<_> = crate::plugins::discover()
+		.into_iter()
+		.filter(|p| crate::cli::find_subcommand(&cli, &p.name).is_none())
+		.collect();
+	let sections: Vec<String> = [
+		aliases.help_section(),
+		crate::plugins::help_section(&plugins),
+	]
+	.into_iter()
+	.flatten()
+	.collect();
+	if !sections.is_empty() {
+		cli = cli.after_long_help(sections.join("\n\n"));
 	}
 
 	println!("{}", cli.render_long_help());
 	Ok(())
 }
 
-fn find_subcommand(cli: &mut Command, name_or_alias: &str) -> Option<Command> {
-	for sub in cli.get_subcommands_mut() {
-		if sub.get_name().eq_ignore_ascii_case(name_or_alias) {
-			return Some(sub.clone());
+fn show(cli: &Command, path: &[String]) -> anyhow::Result<()> {
+	let found = walk(cli, path);
+	if found.len() == path.len()
+		&& let Some(target) = found.last()
+	{
+		println!("{}", (*target).clone().render_long_help());
+		let names: Vec<&str> = found.iter().map(|c| c.get_name()).collect();
+		if let Some(section) = examples_section(cli, &names) {
+			println!("{section}");
 		}
+		return Ok(());
+	}
 
-		for alias in sub.get_all_aliases() {
-			if alias.eq_ignore_ascii_case(name_or_alias) {
-				return Some(sub.clone());
-			}
-		}
+	let first = path.first().map_or("", String::as_str);
+	if found.is_empty()
+		&& let Some(plugin) = crate::plugins::find(first)
+	{
+		println!(
+			"'{first}' is a plugin at {}; try 'eagle {first} --help'",
+			plugin.display()
+		);
+		return Ok(());
+	}
+
+	// The first word that is not a subcommand of the one before it.
+	let parent = found.last().copied().unwrap_or(cli);
+	let known: Vec<&str> = found.iter().map(|c| c.get_name()).collect();
+	let unknown = path.get(found.len()).map_or("", String::as_str);
+	let plugins = crate::plugins::discover();
+	let plugin_names = plugins
+		.iter()
+		.filter(|_| found.is_empty())
+		.map(|p| p.name.as_str());
+	let suggestion = util::closest(
+		unknown,
+		crate::cli::subcommand_names(parent).chain(plugin_names),
+	);
+
+	let mut message = format!("Unknown command: {}", path.join(" "));
+	if let Some(suggestion) = suggestion {
+		let full: Vec<&str> =
+			known.iter().copied().chain([suggestion]).collect();
+		message.push_str(&format!("\n\n  Did you mean: {}?", full.join(" ")));
+	}
+	Err(crate::error::usage(message))
+}
+
+/// Follows `words` through nested subcommands, returning one command per
+/// word until a word does not name a subcommand of the previous one.
+fn walk<'a>(cli: &'a Command, words: &[String]) -> Vec<&'a Command> {
+	let mut found = Vec::new();
+	let mut current = cli;
+	for word in words {
+		let Some(sub) = crate::cli::find_subcommand(current, word) else {
+			break;
+		};
+		found.push(sub);
+		current = sub;
+	}
+	found
+}
+
+/// Renders the examples of the spec that owns `names`, keeping those that
+/// run exactly that (sub)command.
+fn examples_section(cli: &Command, names: &[&str]) -> Option<String> {
+	let top = names.first()?;
+	let spec = crate::commands::iter_specs()
+		.into_iter()
+		.find(|s| s.name == *top)?;
+
+	let lines: Vec<String> = spec
+		.examples
+		.iter()
+		.filter(|e| example_path(cli, spec.name, e.command) == names)
+		.map(|e| format!("  # {}\n  {}", e.about, e.command))
+		.collect();
+	if lines.is_empty() {
+		return None;
 	}
+	Some(format!("Examples:\n{}", lines.join("\n\n")))
+}
 
-	None
+/// The (sub)command an example runs. Examples that do not start with
+/// `eagle` (e.g. shell setup lines) belong to the spec's own command.
+fn example_path<'a>(
+	cli: &'a Command,
+	spec_name: &'a str,
+	command: &str,
+) -> Vec<&'a str> {
+	let mut words = command.split_whitespace();
+	if words.next() != Some("eagle") {
+		return vec![spec_name];
+	}
+	let words: Vec<String> = words.map(str::to_string).collect();
+	walk(cli, &words).iter().map(|c| c.get_name()).collect()
 }
 
 inventory::submit! {
@@ -50,5 +158,15 @@ inventory::submit! {
 		name: "help",
 		command: build,
 		run,
+		examples: &[
+			Example {
+				command: "eagle help minecraft create",
+				about: "Show help for a nested command",
+			},
+		],
+		category: Category::Help,
+		platforms: &[],
+		tools: &[],
+		stability: Stability::Stable,
 	}
 }
@@ -0,0 +1,225 @@
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+use std::time::Duration;
+
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use crossterm::style::Stylize;
+
+use crate::cleanup;
+use crate::commands::{Category, CommandSpec, Example, Stability};
+use crate::context::Context;
+use crate::runlog::{self, Record};
+use crate::ui;
+use crate::util;
+
+const FOLLOW_POLL: Duration = Duration::from_millis(500);
+
+fn build() -> Command {
+	Command::new("logs")
+		.about("Show past runs from the run log")
+		.arg(
+			Arg::new("last")
+				.long("last")
+				.value_name("N")
+				.help("Number of runs to show")
+				.value_parser(clap::value_parser!(usize))
+				.default_value("20"),
+		)
+		.arg(
+			Arg::new("command")
+				.long("command")
+				.value_name("NAME")
+				.help("Only show runs of this command (e.g. minecraft)"),
+		)
+		.arg(
+			Arg::new("failed")
+				.long("failed")
+				.help("Only show runs that exited non-zero")
+				.action(ArgAction::SetTrue),
+		)
+		.arg(
+			Arg::new("follow")
+				.long("follow")
+				.short('f')
+				.help("Keep printing new runs as they are logged")
+				.action(ArgAction::SetTrue),
+		)
+		.arg(
+			Arg::new("details")
+				.long("details")
+				.help("Also show each run's HTTP requests and subprocesses")
+				.action(ArgAction::SetTrue),
+		)
+}
+
+struct Filter<'a> {
+	command: Option<&'a str>,
+	failed: bool,
+}
+
+impl Filter<'_> {
+	fn accepts(&self, record: &Record) -> bool {
+		if self.failed && record.exit_code == 0 {
+			return false;
+		}
+		match self.command {
+			Some(name) => record.command.as_deref() == Some(name),
+			None => true,
+		}
+	}
+}
+
+fn run(matches: &ArgMatches, ctx: &Context) -> anyhow::Result<()> {
+	let path = runlog::default_path(&ctx.config, &ctx.paths)?;
+	let last = matches.get_one::<usize>("last").copied().unwrap_or(20);
+	let filter = Filter {
+		command: matches.get_one::<String>("command").map(String::as_str),
+		failed: matches.get_flag("failed"),
+	};
+	let details = matches.get_flag("details");
+
+	let records: Vec<Record> = runlog::read_all(&path)?
+		.into_iter()
+		.filter(|r| filter.accepts(r))
+		.collect();
+	let records = records
+		.get(records.len().saturating_sub(last)..)
+		.unwrap_or_default();
+
+	if matches.get_flag("follow") {
+		for record in records {
+			print_record(record, details);
+		}
+		return follow(&path, &filter, details);
+	}
+
+	if records.is_empty() && !ui::json_enabled() {
+		ui::muted(&format!("No matching runs in {}", path.display()));
+	}
+	for record in records {
+		print_record(record, details);
+	}
+	ui::result(serde_json::json!({
+		"path": path,
+		"records": records,
+	}));
+	Ok(())
+}
+
+fn print_record(record: &Record, details: bool) {
+	if ui::json_enabled() {
+		ui::event("run", serde_json::to_value(record).unwrap_or_default());
+		return;
+	}
+
+	let args: Vec<&str> = record.args.iter().map(String::as_str).collect();
+	let exit = format!("{:>3}", record.exit_code);
+	let exit = match (ui::stdout_colors(), record.exit_code) {
+		(false, _) => exit,
+		(true, 0) => exit.green().to_string(),
+		(true, _) => exit.red().bold().to_string(),
+	};
+	println!(
+		"{}  {exit}  {:>8}  {}",
+		record.time,
+		format!("{}ms", record.duration_ms),
+		util::format_command_line("eagle", &args)
+	);
+
+	let error_lines = record.errors.iter().flat_map(|e| e.lines());
+	for line in error_lines.filter(|l| !l.trim().is_empty()) {
+		println!("      {line}");
+	}
+
+	if details {
+		for http in &record.http {
+			let outcome = match (&http.status, &http.error) {
+				(Some(status), _) => status.to_string(),
+				(None, Some(err)) => err.clone(),
+				(None, None) => "-".to_string(),
+			};
+			println!(
+				"      http: {} {} → {outcome} ({}ms)",
+				http.method, http.url, http.duration_ms
+			);
+		}
+		for process in &record.processes {
+			let args: Vec<&str> =
+				process.args.iter().map(String::as_str).collect();
+			let exit = process
+				.exit_code
+				.map_or_else(|| "-".to_string(), |c| c.to_string());
+			println!(
+				"      run: {} → {exit} ({}ms)",
+				util::format_command_line(&process.program, &args),
+				process.duration_ms
+			);
+		}
+	}
+}
+
+/// Polls the log for appended lines until Ctrl-C, which ends it like any
+/// other successful run. A file that shrank was rotated, so reading restarts
+/// from its beginning.
+fn follow(path: &Path, filter: &Filter, details: bool) -> anyhow::Result<()> {
+	let mut offset = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+	let mut pending = String::new();
+	let interrupts = cleanup::catch();
+
+	loop {
+		if interrupts.wait(FOLLOW_POLL) {
+			return Ok(());
+		}
+
+		let Ok(mut file) = std::fs::File::open(path) else {
+			continue;
+		};
+		let len = file.metadata()?.len();
+		if len < offset {
+			offset = 0;
+			pending.clear();
+		}
+		if len == offset {
+			continue;
+		}
+
+		file.seek(SeekFrom::Start(offset))?;
+		let mut chunk = String::new();
+		file.read_to_string(&mut chunk)?;
+		offset = len;
+		pending.push_str(&chunk);
+
+		// Keep a trailing partial line for the next poll.
+		let complete = match pending.rfind('\n') {
+			Some(i) => pending.drain(..=i).collect::<String>(),
+			None => continue,
+		};
+		for record in runlog::parse_lines(&complete) {
+			if filter.accepts(&record) {
+				print_record(&record, details);
+			}
+		}
+	}
+}
+
+inventory::submit! {
+	CommandSpec {
+		name: "logs",
+		command: build,
+		run,
+		examples: &[
+			Example {
+				command: "eagle logs --failed --last 5",
+				about: "Show the last five failed runs",
+			},
+			Example {
+				command: "eagle logs --command eaglecord -v",
+				about: "Include HTTP requests and subprocesses",
+			},
+		],
+		category: Category::Diagnostics,
+		platforms: &[],
+		tools: &[],
+		stability: Stability::Stable,
+	}
+}
@@ -6,6 +6,11 @@ use dialoguer::{Input, Select};
 use super::fabric;
 use super::fs;
 use super::paper;
+use crate::context::Context;
+use crate::error;
+use crate::exec::Executor;
+use crate::net::Endpoints;
+use crate::timings;
 use crate::ui;
 
 pub(super) fn build_command() -> Command {
@@ -29,7 +34,6 @@ pub(super) fn build_command() -> Command {
 		.arg(
 			Arg::new("version")
 				.long("version")
-				.short('v')
 				.help("Minecraft version (e.g. 1.21.11 or 1.21)")
 				.required(false),
 		)
@@ -75,42 +79,52 @@ impl ServerType {
 	}
 }
 
-pub(super) fn run_create(matches: &ArgMatches) -> anyhow::Result<()> {
-	let name = matches
-		.get_one::<String>("name")
-		.map(|s| s.to_string())
-		.unwrap_or_else(prompt_server_name);
+pub(super) fn run_create(
+	matches: &ArgMatches,
+	ctx: &Context,
+) -> anyhow::Result<()> {
+	let name = match matches.get_one::<String>("name") {
+		Some(v) => v.to_string(),
+		None => prompt_server_name()?,
+	};
 
 	validate_server_name(&name)?;
 
-	let server_type = matches
-		.get_one::<String>("type")
-		.map(|s| s.as_str())
-		.map(parse_server_type)
-		.transpose()?
-		.unwrap_or_else(select_server_type);
+	let server_type = match matches.get_one::<String>("type") {
+		Some(v) => parse_server_type(v)?,
+		None => select_server_type()?,
+	};
 
-	let version_input = matches
-		.get_one::<String>("version")
-		.map(|s| s.to_string())
-		.unwrap_or_else(prompt_version);
+	let version_input = match matches.get_one::<String>("version") {
+		Some(v) => v.to_string(),
+		None => prompt_version()?,
+	};
 
+	let endpoints = Endpoints::from_config(&ctx.config)?;
 	let version = match server_type {
-		ServerType::Paper => paper::resolve_paper_version(&version_input)?,
+		ServerType::Paper => timings::phase("resolve Paper version", || {
+			paper::resolve_paper_version(&version_input, &endpoints)
+		})?,
 		ServerType::Fabric => version_input.clone(),
 	};
 
-	let port = *matches.get_one::<u16>("port").unwrap_or(&22222);
-	let motd = matches
-		.get_one::<String>("motd")
-		.cloned()
-		.unwrap_or_default();
+	let port = super::arg_or_config(
+		matches,
+		"port",
+		ctx.config.u16("minecraft.port")?,
+	)?;
+	let motd = super::arg_or_config(
+		matches,
+		"motd",
+		ctx.config.string("minecraft.motd")?,
+	)?;
 
 	let force = matches.get_flag("force");
 	let skip_download = matches.get_flag("skip_download");
 
-	let root = fs::servers_root()?;
-	std::fs::create_dir_all(&root)?;
+	let exec = ctx.exec.as_ref();
+	let root = fs::servers_root(&ctx.config, &ctx.paths)?;
+	exec.create_dir_all(&root)?;
 
 	let server_dir = root.join(&name);
 	if server_dir.exists() {
@@ -120,24 +134,28 @@ pub(super) fn run_create(matches: &ArgMatches) -> anyhow::Result<()> {
 				server_dir.display()
 			);
 		}
-		std::fs::remove_dir_all(&server_dir)?;
+		exec.remove_dir_all(&server_dir)?;
 	}
 
-	std::fs::create_dir_all(&server_dir)?;
-	let mut guard = fs::DirGuard::new(server_dir.clone());
+	exec.create_dir_all(&server_dir)?;
+	// A dry run leaves an existing folder in place; never clean that up.
+	let mut guard =
+		(!exec.dry_run()).then(|| fs::DirGuard::new(server_dir.clone()));
 
-	write_eula(&server_dir)?;
-	write_server_properties(&server_dir, port, &motd)?;
+	timings::phase("write server files", || {
+		write_eula(exec, &server_dir)?;
+		write_server_properties(exec, &server_dir, port, &motd)
+	})?;
 
 	if !skip_download {
 		let jar_path = server_dir.join("server.jar");
 		match server_type {
-			ServerType::Paper => {
-				paper::download_paper_server(&version, &jar_path)?
-			}
-			ServerType::Fabric => {
-				fabric::download_fabric_server(&version, &jar_path)?
-			}
+			ServerType::Paper => paper::download_paper_server(
+				&version, &jar_path, &endpoints, exec,
+			)?,
+			ServerType::Fabric => fabric::download_fabric_server(
+				&version, &jar_path, &endpoints, exec,
+			)?,
 		}
 	} else {
 		ui::warning(
@@ -153,8 +171,20 @@ pub(super) fn run_create(matches: &ArgMatches) -> anyhow::Result<()> {
 	));
 	ui::muted(&format!("Port: {port}"));
 	ui::muted(&format!("Motd: {motd}"));
-
-	guard.commit();
+	ui::result(serde_json::json!({
+		"path": server_dir,
+		"name": name,
+		"type": server_type.as_str(),
+		"requested_version": version_input,
+		"version": version,
+		"port": port,
+		"motd": motd,
+		"jar_downloaded": !skip_download,
+	}));
+
+	if let Some(guard) = guard.as_mut() {
+		guard.commit();
+	}
 	Ok(())
 }
 
@@ -166,74 +196,83 @@ fn format_version_label(input: &str, resolved: &str) -> String {
 	}
 }
 
-fn prompt_server_name() -> String {
-	Input::<String>::new()
+fn prompt_server_name() -> anyhow::Result<String> {
+	ui::require_interactive("a server name", "--name <NAME>")?;
+	let theme = ui::theme();
+	Ok(Input::<String>::with_theme(theme.as_ref())
 		.with_prompt("Server name")
-		.interact_text()
-		.unwrap_or_else(|_| "mc-server".to_string())
+		.default("mc-server".to_string())
+		.interact_text()?)
 }
 
-fn prompt_version() -> String {
-	Input::<String>::new()
+fn prompt_version() -> anyhow::Result<String> {
+	ui::require_interactive("a Minecraft version", "--version <VERSION>")?;
+	let theme = ui::theme();
+	Ok(Input::<String>::with_theme(theme.as_ref())
 		.with_prompt("Minecraft version (e.g. 1.21.11 or 1.21)")
-		.interact_text()
-		.unwrap_or_else(|_| "1.21.11".to_string())
+		.default("1.21.11".to_string())
+		.interact_text()?)
 }
 
-fn select_server_type() -> ServerType {
+fn select_server_type() -> anyhow::Result<ServerType> {
+	ui::require_interactive("a server type", "--type <paper|fabric>")?;
 	let options = ["paper", "fabric"];
-	let selection = Select::new()
+	let theme = ui::theme();
+	let selection = Select::with_theme(theme.as_ref())
 		.with_prompt("Server type")
 		.items(&options)
 		.default(0)
-		.interact()
-		.unwrap_or(0);
+		.interact()?;
 
-	if selection == 1 {
+	Ok(if selection == 1 {
 		ServerType::Fabric
 	} else {
 		ServerType::Paper
-	}
+	})
 }
 
 fn parse_server_type(s: &str) -> anyhow::Result<ServerType> {
 	match s.to_lowercase().as_str() {
 		"paper" => Ok(ServerType::Paper),
 		"fabric" => Ok(ServerType::Fabric),
-		_ => anyhow::bail!("Invalid type: {s} (expected: paper | fabric)"),
+		_ => Err(error::unknown_value(
+			format!("Invalid type: {s} (expected: paper | fabric)"),
+			s,
+			["paper", "fabric"],
+		)),
 	}
 }
 
 fn validate_server_name(name: &str) -> anyhow::Result<()> {
 	if name.trim().is_empty() {
-		anyhow::bail!("Name must not be empty");
+		return Err(error::usage("Name must not be empty"));
 	}
 
 	let invalid = ['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
 	if name.chars().any(|c| invalid.contains(&c)) {
-		anyhow::bail!(
-			"Invalid name. Windows folder names cannot contain: <>:\"/\\|?*"
-		);
+		return Err(error::usage(
+			"Invalid name. Windows folder names cannot contain: <>:\"/\\|?*",
+		));
 	}
 
 	if name.contains("..") {
-		anyhow::bail!("Invalid name: '..' not allowed");
+		return Err(error::usage("Invalid name: '..' not allowed"));
 	}
 
 	Ok(())
 }
 
-fn write_eula(server_dir: &Path) -> anyhow::Result<()> {
-	std::fs::write(
-		server_dir.join("eula.txt"),
-		"# By changing the setting below to TRUE you are indicating your\n\
-		 # agreement to our EULA (https://aka.ms/MinecraftEULA).\n\
-		 eula=true\n",
-	)?;
-	Ok(())
+fn write_eula(exec: &dyn Executor, server_dir: &Path) -> anyhow::Result<()> {
+	exec.write(
+		&server_dir.join("eula.txt"),
+		b"# By changing the setting below to TRUE you are indicating your\n\
+		  # agreement to our EULA (https://aka.ms/MinecraftEULA).\n\
+		  eula=true\n",
+	)
 }
 
 fn write_server_properties(
+	exec: &dyn Executor,
 	server_dir: &Path,
 	port: u16,
 	motd: &str,
@@ -268,7 +307,5 @@ fn write_server_properties(
 		 use-native-transport=true\n"
 	);
 
-	std::fs::write(server_dir.join("server.properties"), content)?;
-
-	Ok(())
+	exec.write(&server_dir.join("server.properties"), content.as_bytes())
 }
@@ -2,10 +2,13 @@ use std::path::Path;
 
 use serde::Deserialize;
 
-use crate::net;
+use crate::error;
+use crate::exec::Executor;
+use crate::net::{self, Endpoints};
+use crate::timings;
 use crate::ui;
 
-/// Minimal shape of `GET https://meta.fabricmc.net/v2/versions/loader/{game_version}`.
+/// Minimal shape of `GET {fabric_meta}/v2/versions/loader/{game_version}`.
 #[derive(Debug, Clone, Deserialize)]
 pub struct LoaderCombo {
 	pub loader: LoaderPart,
@@ -29,13 +32,18 @@ pub struct InstallerPart {
 pub(super) fn download_fabric_server(
 	version: &str,
 	jar_path: &Path,
+	endpoints: &Endpoints,
+	exec: &dyn Executor,
 ) -> anyhow::Result<()> {
 	ui::info(&format!("Downloading Fabric {version}..."));
 
-	let url = format!("https://meta.fabricmc.net/v2/versions/loader/{version}");
-	let combos = net::get_json::<Vec<LoaderCombo>>(&url)?;
+	let base = &endpoints.fabric_meta;
+	let url = format!("{base}/v2/versions/loader/{version}");
+	let combos = timings::phase("fetch Fabric loaders", || {
+		net::get_json::<Vec<LoaderCombo>>(&url)
+	})?;
 	if combos.is_empty() {
-		anyhow::bail!("No Fabric loader versions found for {version}");
+		return Err(unknown_game_version(version, endpoints));
 	}
 
 	let best = pick_best_combo(&combos)
@@ -45,18 +53,38 @@ pub(super) fn download_fabric_server(
 	let installer = &best.installer.version;
 
 	let url = format!(
-		"https://meta.fabricmc.net/v2/versions/loader/{version}/{loader}/{installer}/server/jar"
+		"{base}/v2/versions/loader/{version}/{loader}/{installer}/server/jar"
 	);
 
-	if let Some(sha256) = fetch_optional_sha256_for_url(&url) {
-		net::download_to_file_with_sha256(&url, jar_path, &sha256)?;
-	} else {
+	let sha256 = fetch_optional_sha256_for_url(&url);
+	if sha256.is_none() {
 		ui::warning(
 			"No checksum endpoint found for this Fabric artifact; downloading without digest verification.",
 		);
-		net::download_to_file(&url, jar_path)?;
 	}
-	Ok(())
+	timings::phase("download server jar", || {
+		exec.download(&url, jar_path, sha256.as_deref())
+	})
+}
+
+/// Minimal shape of one entry in `GET {fabric_meta}/v2/versions/game`.
+#[derive(Debug, Clone, Deserialize)]
+struct GameVersion {
+	version: String,
+}
+
+/// Fabric has no loader for `version`; suggests a game version it does know.
+fn unknown_game_version(version: &str, endpoints: &Endpoints) -> anyhow::Error {
+	let message = format!("No Fabric loader versions found for {version}");
+	let url = format!("{}/v2/versions/game", endpoints.fabric_meta);
+	match net::get_json::<Vec<GameVersion>>(&url) {
+		Ok(games) => error::unknown_value(
+			message,
+			version,
+			games.iter().map(|g| g.version.as_str()),
+		),
+		Err(_) => anyhow::anyhow!(message),
+	}
 }
 
 fn fetch_optional_sha256_for_url(url: &str) -> Option<String> {
@@ -1,16 +1,17 @@
 use std::path::{Path, PathBuf};
 
-pub fn documents_dir() -> Option<PathBuf> {
-	directories::UserDirs::new()
-		.and_then(|u| u.document_dir().map(|p| p.to_path_buf()))
-}
-
-pub fn servers_root() -> anyhow::Result<PathBuf> {
-	let root = documents_dir()
-		.ok_or_else(|| anyhow::anyhow!("Could not resolve Documents dir"))?
-		.join("mc-servers");
+pub use crate::cleanup::DirGuard;
+use crate::config::Config;
+use crate::paths::Paths;
+
+/// Returns `minecraft.servers_root` from env/config, or `mc-servers` in
+/// `EAGLE_HOME` or Documents.
+pub fn servers_root(config: &Config, paths: &Paths) -> anyhow::Result<PathBuf> {
+	if let Some(root) = config.path_value("minecraft.servers_root")? {
+		return Ok(root);
+	}
 
-	Ok(root)
+	paths.servers_root()
 }
 
 pub fn find_servers(root: &Path) -> anyhow::Result<Vec<PathBuf>> {
@@ -41,31 +42,3 @@ pub fn find_servers(root: &Path) -> anyhow::Result<Vec<PathBuf>> {
 	out.sort();
 	Ok(out)
 }
-
-pub struct DirGuard {
-	path: PathBuf,
-	committed: bool,
-}
-
-impl DirGuard {
-	pub fn new(path: PathBuf) -> Self {
-		Self {
-			path,
-			committed: false,
-		}
-	}
-
-	pub fn commit(&mut self) {
-		self.committed = true;
-	}
-}
-
-impl Drop for DirGuard {
-	fn drop(&mut self) {
-		if self.committed {
-			return;
-		}
-
-		let _ = std::fs::remove_dir_all(&self.path);
-	}
-}
@@ -1,9 +1,11 @@
 use std::cmp::Ordering;
 
-use clap::{Arg, ArgMatches, Command};
+use clap::parser::ValueSource;
+use clap::{ArgMatches, Command};
 
-use crate::commands::CommandSpec;
+use crate::commands::{Category, CommandSpec, Example, JAVA, Stability};
 use crate::context::Context;
+use crate::error;
 
 mod create;
 pub mod fabric;
@@ -15,29 +17,81 @@ fn build() -> Command {
 	Command::new("minecraft")
 		.about("Minecraft server tools (start, create)")
 		.alias("m")
-		.arg(
-			Arg::new("ram_mb")
-				.long("ram-mb")
-				.help("RAM in MB")
-				.value_parser(clap::value_parser!(u32))
-				.required(false),
-		)
+		.arg(start::ram_mb_arg())
+		.subcommand(start::build_command())
 		.subcommand(create::build_command())
 }
 
-fn run(matches: &ArgMatches, _: &Context) -> anyhow::Result<()> {
+fn run(matches: &ArgMatches, ctx: &Context) -> anyhow::Result<()> {
 	match matches.subcommand() {
-		Some(("create", sub)) => create::run_create(sub),
+		Some(("start", sub)) => {
+			if ctx.processes.which(JAVA.name).is_none() {
+				return Err(error::missing_tool(JAVA.name, JAVA.hint));
+			}
+			let server = sub.get_one::<String>("server");
+			// `--ram-mb` works before and after `start`; the later one wins.
+			let ram_from = if from_cli(sub, "ram_mb") {
+				sub
+			} else {
+				matches
+			};
+			start::run_start(ram_from, server.map(String::as_str), ctx)
+		}
+		Some(("create", sub)) => create::run_create(sub, ctx),
 		Some((other, _)) => anyhow::bail!("Unknown subcommand: {other}"),
-		None => start::run_start(matches),
+		None => start::run_start(matches, None, ctx),
+	}
+}
+
+/// The value of `id`: from the command line if given there, otherwise
+/// `configured`, otherwise the arg's `default_value`. Keeps the default in
+/// help and docs while config still wins over it.
+fn arg_or_config<T: Clone + Send + Sync + 'static>(
+	matches: &ArgMatches,
+	id: &str,
+	configured: Option<T>,
+) -> anyhow::Result<T> {
+	let from_cli = from_cli(matches, id);
+	match configured {
+		Some(value) if !from_cli => Ok(value),
+		_ => matches
+			.get_one::<T>(id)
+			.cloned()
+			.ok_or_else(|| anyhow::anyhow!("missing --{id}")),
 	}
 }
 
+fn from_cli(matches: &ArgMatches, id: &str) -> bool {
+	matches.value_source(id) == Some(ValueSource::CommandLine)
+}
+
 inventory::submit! {
 	CommandSpec {
 		name: "minecraft",
 		command: build,
 		run,
+		examples: &[
+			Example {
+				command: "eagle minecraft",
+				about: "Pick a server and start it",
+			},
+			Example {
+				command: "eagle minecraft start lobby --ram-mb 4096",
+				about: "Start the server in the 'lobby' folder with 4 GB RAM",
+			},
+			Example {
+				command: "eagle minecraft create --name lobby --type paper --version 1.21",
+				about: "Create a Paper server on the newest 1.21.x build",
+			},
+			Example {
+				command: "eagle minecraft create --name modded --type fabric --version 1.21.11 --port 25566",
+				about: "Create a Fabric server on another port",
+			},
+		],
+		category: Category::Workflows,
+		platforms: &[],
+		tools: &[JAVA],
+		stability: Stability::Stable,
 	}
 }
 
@@ -3,27 +3,38 @@ use std::path::Path;
 
 use serde::Deserialize;
 
-use crate::net;
+use crate::error;
+use crate::exec::Executor;
+use crate::net::{self, Endpoints};
+use crate::timings;
 use crate::ui;
 
-/// Minimal shape of `GET https://fill.papermc.io/v3/projects/paper`.
+/// Minimal shape of `GET {paper_api}/v3/projects/paper`.
 #[derive(Debug, Clone, Deserialize)]
 struct FillProjectIndex {
 	versions: HashMap<String, Vec<String>>,
 }
 
-pub(super) fn resolve_paper_version(version: &str) -> anyhow::Result<String> {
+pub(super) fn resolve_paper_version(
+	version: &str,
+	endpoints: &Endpoints,
+) -> anyhow::Result<String> {
 	let version = version.trim();
 	if !looks_like_family_key(version) {
 		return Ok(version.to_string());
 	}
 
-	let index = net::get_json::<FillProjectIndex>(
-		"https://fill.papermc.io/v3/projects/paper",
-	)?;
+	let index = net::get_json::<FillProjectIndex>(&format!(
+		"{}/v3/projects/paper",
+		endpoints.paper_api
+	))?;
 
 	let versions = index.versions.get(version).ok_or_else(|| {
-		anyhow::anyhow!("Unknown Paper version family: {version}")
+		error::unknown_value(
+			format!("Unknown Paper version family: {version}"),
+			version,
+			index.versions.keys().map(String::as_str),
+		)
 	})?;
 
 	let best = pick_best_version_for_family(versions).ok_or_else(|| {
@@ -82,13 +93,18 @@ pub struct FillChecksums {
 pub(super) fn download_paper_server(
 	version: &str,
 	jar_path: &Path,
+	endpoints: &Endpoints,
+	exec: &dyn Executor,
 ) -> anyhow::Result<()> {
 	ui::info(&format!("Downloading Paper {version}..."));
 
 	let url = format!(
-		"https://fill.papermc.io/v3/projects/paper/versions/{version}/builds"
+		"{}/v3/projects/paper/versions/{version}/builds",
+		endpoints.paper_api
 	);
-	let builds = net::get_json::<Vec<FillBuild>>(&url)?;
+	let builds = timings::phase("fetch Paper builds", || {
+		net::get_json::<Vec<FillBuild>>(&url)
+	})?;
 	if builds.is_empty() {
 		anyhow::bail!("No Paper builds found for {version}");
 	}
@@ -107,12 +123,9 @@ pub(super) fn download_paper_server(
 		best.id, download.name, download.checksums.sha256
 	));
 
-	net::download_to_file_with_sha256(
-		&download.url,
-		jar_path,
-		&download.checksums.sha256,
-	)?;
-	Ok(())
+	timings::phase("download server jar", || {
+		exec.download(&download.url, jar_path, Some(&download.checksums.sha256))
+	})
 }
 
 #[doc(hidden)]
@@ -1,24 +1,88 @@
+use std::io::IsTerminal as _;
 use std::path::Path;
 
-use clap::ArgMatches;
+use clap::{Arg, ArgMatches, Command};
+use clap_complete::engine::{ArgValueCandidates, CompletionCandidate};
 use dialoguer::Select;
 
 use super::fs;
+use crate::config::Config;
+use crate::context::Context;
+use crate::error;
+use crate::paths::Paths;
 use crate::ui;
 
-pub(super) fn run_start(matches: &ArgMatches) -> anyhow::Result<()> {
-	if which::which("java").is_err() {
-		anyhow::bail!("java not found in PATH");
-	}
+pub(super) fn build_command() -> Command {
+	Command::new("start")
+		.about("Start a server, or pick one when no name is given")
+		.arg(
+			Arg::new("server")
+				.help("Server to start (skips the picker)")
+				.required(false)
+				.add(ArgValueCandidates::new(server_candidates)),
+		)
+		.arg(ram_mb_arg())
+}
+
+/// `--ram-mb`, on both `minecraft` and `minecraft start`.
+pub(super) fn ram_mb_arg() -> Arg {
+	Arg::new("ram_mb")
+		.long("ram-mb")
+		.help("RAM in MB")
+		.value_parser(clap::value_parser!(u32))
+		.default_value("8192")
+}
+
+fn server_candidates() -> Vec<CompletionCandidate> {
+	let config = Config::load_default();
+	let Ok(root) = fs::servers_root(&config, &Paths::from_env()) else {
+		return Vec::new();
+	};
+
+	fs::find_servers(&root)
+		.unwrap_or_default()
+		.iter()
+		.filter_map(|p| p.file_name()?.to_str().map(CompletionCandidate::new))
+		.collect()
+}
 
-	let ram_mb = *matches.get_one::<u32>("ram_mb").unwrap_or(&8192);
+/// Starts `server`, or the one picked from a list when it is `None`.
+pub(super) fn run_start(
+	matches: &ArgMatches,
+	server: Option<&str>,
+	ctx: &Context,
+) -> anyhow::Result<()> {
+	let ram_mb = super::arg_or_config(
+		matches,
+		"ram_mb",
+		ctx.config.u32("minecraft.ram_mb")?,
+	)?;
 
-	let root = fs::servers_root()?;
+	let root = fs::servers_root(&ctx.config, &ctx.paths)?;
 	let servers = fs::find_servers(&root)?;
 	if servers.is_empty() {
 		anyhow::bail!("No servers found in: {}", root.display());
 	}
 
+	if let Some(wanted) = server {
+		let server_path = servers
+			.iter()
+			.find(|p| p.file_name().is_some_and(|n| n == wanted))
+			.ok_or_else(|| {
+				error::unknown_value(
+					format!(
+						"Server '{wanted}' not found in: {}",
+						root.display()
+					),
+					wanted,
+					servers
+						.iter()
+						.filter_map(|p| p.file_name().and_then(|n| n.to_str())),
+				)
+			})?;
+		return launch(ctx, server_path, wanted, ram_mb);
+	}
+
 	let items: Vec<String> = servers
 		.iter()
 		.map(|p| {
@@ -34,7 +98,12 @@ pub(super) fn run_start(matches: &ArgMatches) -> anyhow::Result<()> {
 		})
 		.collect();
 
-	let selection = Select::new()
+	ui::require_interactive(
+		"a server to start",
+		"its name, e.g. `eagle minecraft start <SERVER>`",
+	)?;
+	let theme = ui::theme();
+	let selection = Select::with_theme(theme.as_ref())
 		.with_prompt("Select a Minecraft server")
 		.items(&items)
 		.default(0)
@@ -46,6 +115,15 @@ pub(super) fn run_start(matches: &ArgMatches) -> anyhow::Result<()> {
 	let item_name = items.get(selection).ok_or_else(|| {
 		anyhow::anyhow!("dialoguer returned out-of-bounds index {selection}")
 	})?;
+	launch(ctx, server_path, item_name, ram_mb)
+}
+
+fn launch(
+	ctx: &Context,
+	server_path: &Path,
+	item_name: &str,
+	ram_mb: u32,
+) -> anyhow::Result<()> {
 	let jar_path = server_path.join("server.jar");
 	if !jar_path.exists() {
 		anyhow::bail!(
@@ -53,25 +131,29 @@ pub(super) fn run_start(matches: &ArgMatches) -> anyhow::Result<()> {
 		);
 	}
 
-	crossterm::execute!(
-		std::io::stdout(),
-		crossterm::terminal::SetTitle(format!("MC-SERVER: {item_name}"))
-	)?;
+	if !ui::json_enabled() && std::io::stdout().is_terminal() {
+		crossterm::execute!(
+			std::io::stdout(),
+			crossterm::terminal::SetTitle(format!("MC-SERVER: {item_name}"))
+		)?;
+	}
 
 	let java_args = build_java_args(ram_mb, &jar_path);
-	let status = std::process::Command::new("java")
-		.args(java_args)
-		.current_dir(server_path)
-		.stdin(std::process::Stdio::inherit())
-		.stdout(std::process::Stdio::inherit())
-		.stderr(std::process::Stdio::inherit())
-		.status()?;
+	let java_args: Vec<&str> = java_args.iter().map(String::as_str).collect();
+	let status = ctx
+		.exec
+		.run_inherit("java", &java_args, Some(server_path))?;
 
 	if !status.success() {
-		anyhow::bail!("java exited with: {status}");
+		return Err(error::process_failed("java", status));
 	}
 
 	ui::success("Server stopped.");
+	ui::result(serde_json::json!({
+		"server": item_name,
+		"path": server_path,
+		"ram_mb": ram_mb,
+	}));
 	Ok(())
 }
 
@@ -1,11 +1,210 @@
 use clap::{ArgMatches, Command};
 
 use crate::context::Context;
+use crate::error::{self, Error};
+use crate::process::ProcessRunner;
+use crate::ui;
 
 pub struct CommandSpec {
 	pub name: &'static str,
 	pub command: fn() -> Command,
 	pub run: fn(&ArgMatches, &Context) -> anyhow::Result<()>,
+	/// Shown by `eagle help`, under the (sub)command each one invokes.
+	pub examples: &'static [Example],
+	/// Heading the command is listed under in `eagle help`.
+	pub category: Category,
+	/// Platforms the command works on; empty means all.
+	pub platforms: &'static [Platform],
+	/// External tools the command needs when run without a subcommand.
+	/// Subcommands (like `minecraft create`) check their own.
+	pub tools: &'static [Tool],
+	pub stability: Stability,
+}
+
+/// A usage example: a full `eagle ...` command line and what it does.
+pub struct Example {
+	pub command: &'static str,
+	pub about: &'static str,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+	Workflows,
+	Setup,
+	Diagnostics,
+	SelfManagement,
+	Help,
+}
+
+impl Category {
+	/// Every category, in the order `eagle help` lists them.
+	pub const ALL: [Self; 5] = [
+		Self::Workflows,
+		Self::Setup,
+		Self::Diagnostics,
+		Self::SelfManagement,
+		Self::Help,
+	];
+
+	pub fn heading(self) -> &'static str {
+		match self {
+			Self::Workflows => "Workflows",
+			Self::Setup => "Setup",
+			Self::Diagnostics => "Diagnostics",
+			Self::SelfManagement => "Self-management",
+			Self::Help => "Help",
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Platform {
+	MacOs,
+	Linux,
+	Windows,
+}
+
+impl Platform {
+	pub fn current() -> Option<Self> {
+		if cfg!(target_os = "macos") {
+			Some(Self::MacOs)
+		} else if cfg!(target_os = "linux") {
+			Some(Self::Linux)
+		} else if cfg!(target_os = "windows") {
+			Some(Self::Windows)
+		} else {
+			None
+		}
+	}
+
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::MacOs => "macOS",
+			Self::Linux => "Linux",
+			Self::Windows => "Windows",
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stability {
+	Stable,
+	/// Listed with a marker; warns when run.
+	Experimental,
+	/// Runs, but is left out of help and completions.
+	Hidden,
+}
+
+/// An external program a command runs, with how to install it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tool {
+	pub name: &'static str,
+	pub hint: &'static str,
+}
+
+pub const GIT: Tool = Tool {
+	name: "git",
+	hint: "Install git: https://git-scm.com/downloads (macOS: xcode-select --install)",
+};
+
+pub const BUN: Tool = Tool {
+	name: "bun",
+	hint: "Install Bun: curl -fsSL https://bun.sh/install | bash (or brew install oven-sh/bun/bun)",
+};
+
+pub const JAVA: Tool = Tool {
+	name: "java",
+	hint: "Install a Java 21+ runtime, e.g. https://adoptium.net or brew install openjdk@21",
+};
+
+impl CommandSpec {
+	/// Whether [`CommandSpec::platforms`] allows `platform`.
+	pub fn supports(&self, platform: Option<Platform>) -> bool {
+		self.platforms.is_empty()
+			|| platform.is_some_and(|p| self.platforms.contains(&p))
+	}
+
+	pub fn missing_tools(&self, processes: &dyn ProcessRunner) -> Vec<Tool> {
+		self.tools
+			.iter()
+			.filter(|t| processes.which(t.name).is_none())
+			.copied()
+			.collect()
+	}
+
+	/// Rejects the run before [`CommandSpec::run`] when this platform is
+	/// unsupported or a required tool is missing; warns for experimental
+	/// commands.
+	pub fn check(
+		&self,
+		matches: &ArgMatches,
+		ctx: &Context,
+	) -> anyhow::Result<()> {
+		require_platform(&format!("'{}'", self.name), self.platforms)?;
+
+		if matches.subcommand().is_none()
+			&& let Some(tool) =
+				self.missing_tools(ctx.processes.as_ref()).first()
+		{
+			return Err(error::missing_tool(tool.name, tool.hint));
+		}
+
+		if self.stability == Stability::Experimental {
+			ui::warning(&format!(
+				"'{}' is experimental; its flags and output may change",
+				self.name
+			));
+		}
+		Ok(())
+	}
+
+	/// Short notes for the help listing: unsupported platform, missing
+	/// tools, experimental. Tools are only checked when `processes` is
+	/// given.
+	pub fn marks(&self, processes: Option<&dyn ProcessRunner>) -> Vec<String> {
+		let mut marks = Vec::new();
+		if !self.supports(Platform::current()) {
+			let supported: Vec<&str> =
+				self.platforms.iter().map(|p| p.as_str()).collect();
+			marks.push(format!("{} only", supported.join("/")));
+		}
+		let missing: Vec<&str> = processes
+			.map(|p| self.missing_tools(p))
+			.unwrap_or_default()
+			.iter()
+			.map(|t| t.name)
+			.collect();
+		if !missing.is_empty() {
+			marks.push(format!("needs {}", missing.join(", ")));
+		}
+		if self.stability == Stability::Experimental {
+			marks.push("experimental".to_string());
+		}
+		marks
+	}
+}
+
+/// Rejects `what` (e.g. `'uninstall'`) unless this platform is one of
+/// `platforms`; empty means all.
+pub fn require_platform(
+	what: &str,
+	platforms: &[Platform],
+) -> anyhow::Result<()> {
+	if platforms.is_empty()
+		|| Platform::current().is_some_and(|p| platforms.contains(&p))
+	{
+		return Ok(());
+	}
+
+	let supported: Vec<&str> = platforms.iter().map(|p| p.as_str()).collect();
+	Err(Error::new(
+		error::Category::Usage,
+		format!(
+			"{what} is not available on this platform (supported: {})",
+			supported.join(", ")
+		),
+	)
+	.into())
 }
 
 inventory::collect!(CommandSpec);
@@ -14,9 +213,15 @@ pub fn iter_specs() -> inventory::iter<CommandSpec> {
 	inventory::iter::<CommandSpec>
 }
 
+pub mod changelog;
+pub mod completions;
+pub mod config;
 pub mod create;
+pub mod docs;
+pub mod doctor;
 pub mod eaglecord;
 pub mod help;
+pub mod logs;
 pub mod minecraft;
 pub mod uninstall;
 pub mod update;
@@ -1,14 +1,14 @@
 use clap::{Arg, ArgMatches, Command};
 use dialoguer::Confirm;
 
-use crate::commands::CommandSpec;
+use crate::commands::{Category, CommandSpec, Example, Platform, Stability};
 use crate::context::Context;
 use crate::ui;
 use crate::util;
 
 fn build() -> Command {
 	Command::new("uninstall")
-		.about("Uninstall eagle (macOS)")
+		.about("Uninstall eagle")
 		.alias("rem")
 		.arg(
 			Arg::new("yes")
@@ -34,12 +34,15 @@ fn run(matches: &ArgMatches, ctx: &Context) -> anyhow::Result<()> {
 	}
 
 	if !yes {
-		let confirmed = Confirm::new()
+		ui::require_interactive("confirmation", "--yes")?;
+		let theme = ui::theme();
+		let confirmed = Confirm::with_theme(theme.as_ref())
 			.with_prompt("Uninstall eagle?")
 			.default(false)
 			.interact()?;
 		if !confirmed {
 			ui::muted("Uninstall canceled.");
+			ui::result(serde_json::json!({ "scheduled": false }));
 			return Ok(());
 		}
 	}
@@ -54,11 +57,15 @@ fn run(matches: &ArgMatches, ctx: &Context) -> anyhow::Result<()> {
 rm -f '{exe_path}'"
 	);
 
-	util::spawn_shell_background(&script)?;
+	ctx.exec.spawn_shell_background(&script)?;
 
 	ui::success(
 		"Uninstall scheduled. Close this shell if eagle is still in use.",
 	);
+	ui::result(serde_json::json!({
+		"scheduled": true,
+		"exe_path": ctx.exe_path,
+	}));
 	Ok(())
 }
 
@@ -67,5 +74,13 @@ inventory::submit! {
 		name: "uninstall",
 		command: build,
 		run,
+		examples: &[Example {
+			command: "eagle uninstall --yes",
+			about: "Remove eagle without asking",
+		}],
+		category: Category::SelfManagement,
+		platforms: &[Platform::MacOs, Platform::Linux],
+		tools: &[],
+		stability: Stability::Stable,
 	}
 }
@@ -1,182 +0,0 @@
-use std::path::Path;
-
-use clap::{Arg, ArgMatches, Command};
-use serde::Deserialize;
-
-use crate::commands::CommandSpec;
-use crate::context::Context;
-use crate::net;
-use crate::ui;
-use crate::util;
-
-const RELEASE_API_URL: &str =
-	"https://api.github.com/repos/prodbyeagle/cli/releases/latest";
-
-#[derive(Debug, Deserialize)]
-struct GithubRelease {
-	tag_name: String,
-	assets: Vec<GithubAsset>,
-}
-
-#[derive(Debug, Deserialize)]
-struct GithubAsset {
-	name: String,
-	browser_download_url: String,
-	digest: Option<String>,
-}
-
-fn latest_release() -> anyhow::Result<GithubRelease> {
-	net::get_json::<GithubRelease>(RELEASE_API_URL)
-}
-
-fn expected_asset_name() -> &'static str {
-	if cfg!(target_os = "macos") && cfg!(target_arch = "x86_64") {
-		"eagle-x86_64-apple-darwin"
-	} else if cfg!(target_os = "macos") && cfg!(target_arch = "aarch64") {
-		"eagle-aarch64-apple-darwin"
-	} else if cfg!(target_os = "windows") {
-		"eagle.exe"
-	} else {
-		"eagle"
-	}
-}
-
-fn latest_eagle_asset(release: &GithubRelease) -> anyhow::Result<&GithubAsset> {
-	let name = expected_asset_name();
-	release
-		.assets
-		.iter()
-		.find(|asset| asset.name.eq_ignore_ascii_case(name))
-		.ok_or_else(|| {
-			anyhow::anyhow!("Latest release does not include '{}' binary", name)
-		})
-}
-
-fn build() -> Command {
-	Command::new("update")
-		.about("Update eagle in place (macOS)")
-		.alias("u")
-		.arg(
-			Arg::new("force")
-				.long("force")
-				.help("Run even if this looks like a dev binary")
-				.action(clap::ArgAction::SetTrue),
-		)
-		.arg(
-			Arg::new("dev")
-				.long("dev")
-				.help(
-					"Install a local dev build instead of pulling from GitHub.\n\
-					 Optionally pass the path to the binary; defaults to\n\
-					 ./target/debug/eagle (relative to the current directory).",
-				)
-				.num_args(0..=1)
-				.value_name("PATH")
-				.default_missing_value("target/debug/eagle"),
-		)
-}
-
-fn run(matches: &ArgMatches, ctx: &Context) -> anyhow::Result<()> {
-	let force = matches.get_flag("force");
-
-	if let Some(dev_path_str) = matches.get_one::<String>("dev") {
-		return run_dev_install(dev_path_str, ctx);
-	}
-
-	if is_dev_exe(&ctx.exe_path) && !force {
-		anyhow::bail!("Refusing to self-update a dev binary. Use --force.");
-	}
-
-	let release = latest_release()?;
-	let latest_version = release.tag_name.trim_start_matches('v');
-
-	if latest_version == ctx.version {
-		ui::success(&format!("Already up to date (v{})", ctx.version));
-		return Ok(());
-	}
-
-	let asset = latest_eagle_asset(&release)?;
-	let digest = asset.digest.as_deref().ok_or_else(|| {
-		anyhow::anyhow!("Release asset is missing sha256 digest")
-	})?;
-
-	let new_path = ctx.exe_dir.join("eagle.new");
-	ui::info(&format!(
-		"Updating eagle v{} → v{latest_version}",
-		ctx.version
-	));
-	net::download_to_file_with_sha256(
-		&asset.browser_download_url,
-		&new_path,
-		digest,
-	)?;
-
-	schedule_replace(&new_path, ctx)?;
-	ui::success("Update scheduled. Re-run eagle in a new shell.");
-	Ok(())
-}
-
-fn run_dev_install(dev_path_str: &str, ctx: &Context) -> anyhow::Result<()> {
-	let dev_path = {
-		let p = std::path::PathBuf::from(dev_path_str);
-		if p.is_absolute() {
-			p
-		} else {
-			std::env::current_dir()?.join(p)
-		}
-	};
-
-	if !dev_path.exists() {
-		anyhow::bail!(
-			"Dev binary not found: {}\n\
-			 Build it first with `cargo build --release`, or pass the path explicitly:\n\
-			 eagle update --dev <PATH>",
-			dev_path.display()
-		);
-	}
-
-	let new_path = ctx.exe_dir.join("eagle.new");
-	ui::info(&format!(
-		"Installing dev build: {} → {}",
-		dev_path.display(),
-		ctx.exe_path.display()
-	));
-	std::fs::copy(&dev_path, &new_path)?;
-
-	schedule_replace(&new_path, ctx)?;
-	ui::success("Dev build installed. Re-run eagle in a new shell.");
-	Ok(())
-}
-
-fn schedule_replace(
-	new_path: &std::path::Path,
-	ctx: &Context,
-) -> anyhow::Result<()> {
-	let pid = std::process::id();
-	let exe_path =
-		util::escape_sh_single_quoted(&ctx.exe_path.to_string_lossy());
-	let new_path_s = util::escape_sh_single_quoted(&new_path.to_string_lossy());
-
-	// Wait for the current process to exit, then atomically replace the binary
-	// and ensure it remains executable.
-	let script = format!(
-		"while kill -0 {pid} 2>/dev/null; do sleep 0.1; done; \
-mv -f '{new_path_s}' '{exe_path}'; chmod +x '{exe_path}'"
-	);
-
-	util::spawn_shell_background(&script)
-}
-
-#[doc(hidden)]
-pub fn is_dev_exe(path: &Path) -> bool {
-	let s = path.to_string_lossy().to_lowercase();
-	s.contains("/target/debug/") || s.contains("/target/release/")
-}
-
-inventory::submit! {
-	CommandSpec {
-		name: "update",
-		command: build,
-		run,
-	}
-}
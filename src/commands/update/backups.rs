@@ -0,0 +1,118 @@
+//! Copies of replaced binaries, kept in [`DIR_NAME`] next to the executable
+//! so `update --rollback` can put one back.
+
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::error;
+use crate::exec::Executor;
+
+pub const DIR_NAME: &str = ".eagle-backups";
+
+/// How many backups are kept when `update.keep_backups` is unset.
+pub const DEFAULT_KEEP: u32 = 3;
+
+const PREFIX: &str = "eagle-";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Backup {
+	pub version: String,
+	pub path: PathBuf,
+	pub saved_at: SystemTime,
+}
+
+pub fn dir(exe_dir: &Path) -> PathBuf {
+	exe_dir.join(DIR_NAME)
+}
+
+/// The backups in `dir`, most recently saved first.
+pub fn list(dir: &Path) -> anyhow::Result<Vec<Backup>> {
+	let read_dir = match std::fs::read_dir(dir) {
+		Ok(rd) => rd,
+		Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+			return Ok(Vec::new());
+		}
+		Err(err) => return Err(err.into()),
+	};
+
+	let mut backups = Vec::new();
+	for entry in read_dir {
+		let entry = entry?;
+		let name = entry.file_name();
+		let Some(version) = name.to_str().and_then(|n| n.strip_prefix(PREFIX))
+		else {
+			continue;
+		};
+		let meta = entry.metadata()?;
+		if !meta.is_file() {
+			continue;
+		}
+		backups.push(Backup {
+			version: version.to_string(),
+			path: entry.path(),
+			saved_at: meta.modified()?,
+		});
+	}
+
+	backups.sort_by_key(|b| std::cmp::Reverse(b.saved_at));
+	Ok(backups)
+}
+
+/// Copies `exe` into `dir` as `version`, then deletes all but the `keep`
+/// most recent backups. `keep == 0` turns backups off.
+pub fn save(
+	exec: &dyn Executor,
+	exe: &Path,
+	dir: &Path,
+	version: &str,
+	keep: u32,
+) -> anyhow::Result<Option<PathBuf>> {
+	if keep == 0 {
+		return Ok(None);
+	}
+
+	let older: Vec<Backup> = list(dir)?
+		.into_iter()
+		.filter(|b| b.version != version)
+		.collect();
+
+	exec.create_dir_all(dir)?;
+	let path = dir.join(format!("{PREFIX}{version}"));
+	exec.copy(exe, &path)?;
+
+	let keep_older = usize::try_from(keep - 1).unwrap_or(usize::MAX);
+	for stale in older.iter().skip(keep_older) {
+		exec.remove_file(&stale.path)?;
+	}
+	Ok(Some(path))
+}
+
+/// The backup `--rollback` restores: the one for `wanted` if given,
+/// otherwise the most recent one older than `current`, otherwise the most
+/// recent one of any other version.
+pub fn pick<'a>(
+	backups: &'a [Backup],
+	current: &str,
+	wanted: Option<&str>,
+) -> anyhow::Result<&'a Backup> {
+	if let Some(wanted) = wanted {
+		let wanted = wanted.trim_start_matches('v');
+		return backups.iter().find(|b| b.version == wanted).ok_or_else(|| {
+			error::unknown_value(
+				format!("No backup of v{wanted}"),
+				wanted,
+				backups.iter().map(|b| b.version.as_str()),
+			)
+		});
+	}
+
+	backups
+		.iter()
+		.find(|b| super::is_newer(current, &b.version))
+		.or_else(|| backups.iter().find(|b| b.version != current))
+		.ok_or_else(|| {
+			error::usage(format!(
+				"No backup other than v{current} to roll back to"
+			))
+		})
+}
@@ -0,0 +1,628 @@
+use std::path::Path;
+
+pub mod backups;
+pub mod notes;
+
+use clap::{Arg, ArgMatches, Command};
+use dialoguer::Confirm;
+use semver::Version;
+use serde::Deserialize;
+
+use crate::commands::{
+	self, Category, CommandSpec, Example, Platform, Stability,
+};
+use crate::config::Config;
+use crate::context::Context;
+use crate::error::{self, Error};
+use crate::net::{self, Endpoints};
+use crate::ui;
+use crate::util;
+
+/// Where `update` can replace the running binary (including `--dev` and
+/// `--rollback`). Listing releases and backups works everywhere.
+const INSTALL_PLATFORMS: &[Platform] = &[Platform::MacOs, Platform::Linux];
+
+/// Path of the release list endpoint below `endpoints.github_api`.
+pub(crate) const RELEASES_PATH: &str =
+	"/repos/prodbyeagle/cli/releases?per_page=100";
+
+#[derive(Debug, Deserialize)]
+struct GithubRelease {
+	tag_name: String,
+	#[serde(default)]
+	prerelease: bool,
+	#[serde(default)]
+	draft: bool,
+	#[serde(default)]
+	name: Option<String>,
+	/// Release notes, in Markdown.
+	#[serde(default)]
+	body: Option<String>,
+	#[serde(default)]
+	published_at: Option<String>,
+	assets: Vec<GithubAsset>,
+}
+
+impl GithubRelease {
+	fn version(&self) -> Option<Version> {
+		parse_version(&self.tag_name)
+	}
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubAsset {
+	name: String,
+	browser_download_url: String,
+	digest: Option<String>,
+}
+
+/// Which releases `update` (and the new-release notice) consider.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Channel {
+	Stable,
+	/// Stable releases plus prereleases.
+	Prerelease,
+}
+
+impl Channel {
+	const NAMES: [&str; 2] = ["stable", "prerelease"];
+
+	/// Reads `update.channel`; unset means [`Channel::Stable`].
+	pub fn from_config(config: &Config) -> anyhow::Result<Self> {
+		match config.string("update.channel")?.as_deref() {
+			None | Some("stable") => Ok(Self::Stable),
+			Some("prerelease") => Ok(Self::Prerelease),
+			Some(other) => Err(error::unknown_value(
+				format!(
+					"Unknown update channel '{other}' (expected: stable | prerelease)"
+				),
+				other,
+				Self::NAMES,
+			)),
+		}
+	}
+
+	fn includes(self, release: &GithubRelease) -> bool {
+		self == Self::Prerelease || !release.prerelease
+	}
+}
+
+/// Every published release, read from all pages of the list and sorted
+/// newest version first. Drafts and tags that are not semver are skipped.
+fn releases(endpoints: &Endpoints) -> anyhow::Result<Vec<GithubRelease>> {
+	let releases = net::get_json_pages::<GithubRelease>(&format!(
+		"{}{RELEASES_PATH}",
+		endpoints.github_api
+	))?;
+	Ok(published(releases))
+}
+
+fn published(mut releases: Vec<GithubRelease>) -> Vec<GithubRelease> {
+	releases.retain(|r| !r.draft && r.version().is_some());
+	releases.sort_by_key(|r| std::cmp::Reverse(r.version()));
+	releases
+}
+
+fn newest(
+	releases: &[GithubRelease],
+	channel: Channel,
+) -> anyhow::Result<&GithubRelease> {
+	releases
+		.iter()
+		.find(|r| channel.includes(r))
+		.ok_or_else(|| anyhow::anyhow!("No published releases found"))
+}
+
+/// Version of the newest release on `channel`, without the leading `v`.
+pub(crate) fn latest_version(
+	endpoints: &Endpoints,
+	channel: Channel,
+) -> anyhow::Result<String> {
+	let releases = releases(endpoints)?;
+	Ok(version_label(newest(&releases, channel)?))
+}
+
+/// Like [`latest_version`], but one quiet request that gives up after
+/// `timeout`. It only reads the first page, which holds the newest releases.
+pub(crate) fn latest_version_within(
+	endpoints: &Endpoints,
+	channel: Channel,
+	timeout: std::time::Duration,
+) -> anyhow::Result<String> {
+	let releases = published(net::get_json_once(
+		&format!("{}{RELEASES_PATH}", endpoints.github_api),
+		timeout,
+	)?);
+	Ok(version_label(newest(&releases, channel)?))
+}
+
+/// Parses `1.2.3` or `v1.2.3` as semver.
+#[doc(hidden)]
+pub fn parse_version(s: &str) -> Option<Version> {
+	Version::parse(s.trim().trim_start_matches('v')).ok()
+}
+
+/// Whether `candidate` is a higher semver version than `current`. Versions
+/// that do not parse are never newer.
+#[doc(hidden)]
+pub fn is_newer(candidate: &str, current: &str) -> bool {
+	match (parse_version(candidate), parse_version(current)) {
+		(Some(candidate), Some(current)) => candidate > current,
+		_ => false,
+	}
+}
+
+fn version_label(release: &GithubRelease) -> String {
+	release.tag_name.trim_start_matches('v').to_string()
+}
+
+fn expected_asset_name() -> &'static str {
+	if cfg!(target_os = "macos") && cfg!(target_arch = "x86_64") {
+		"eagle-x86_64-apple-darwin"
+	} else if cfg!(target_os = "macos") && cfg!(target_arch = "aarch64") {
+		"eagle-aarch64-apple-darwin"
+	} else if cfg!(target_os = "windows") {
+		"eagle.exe"
+	} else {
+		"eagle"
+	}
+}
+
+fn latest_eagle_asset(release: &GithubRelease) -> anyhow::Result<&GithubAsset> {
+	let name = expected_asset_name();
+	release
+		.assets
+		.iter()
+		.find(|asset| asset.name.eq_ignore_ascii_case(name))
+		.ok_or_else(|| {
+			anyhow::anyhow!("Latest release does not include '{}' binary", name)
+		})
+}
+
+fn build() -> Command {
+	Command::new("update")
+		.about("Update eagle in place")
+		.alias("u")
+		.arg(
+			Arg::new("force")
+				.long("force")
+				.help("Run even if this looks like a dev binary")
+				.action(clap::ArgAction::SetTrue),
+		)
+		.arg(
+			Arg::new("dev")
+				.long("dev")
+				.help(
+					"Install a local dev build instead of pulling from GitHub.\n\
+					 Optionally pass the path to the binary; defaults to\n\
+					 ./target/debug/eagle (relative to the current directory).",
+				)
+				.num_args(0..=1)
+				.value_name("PATH")
+				.default_missing_value("target/debug/eagle"),
+		)
+		.arg(
+			Arg::new("to")
+				.long("to")
+				.value_name("VERSION")
+				.help("Install this release instead of the newest one (with --rollback: restore this backup)")
+				.conflicts_with("dev"),
+		)
+		.arg(
+			Arg::new("list")
+				.long("list")
+				.help("List published releases and exit")
+				.action(clap::ArgAction::SetTrue)
+				.conflicts_with_all(["dev", "to"]),
+		)
+		.arg(
+			Arg::new("rollback")
+				.long("rollback")
+				.help("Restore the binary the last update replaced")
+				.action(clap::ArgAction::SetTrue)
+				.conflicts_with_all(["dev", "list"]),
+		)
+		.arg(
+			Arg::new("list_installed")
+				.long("list-installed")
+				.help("List the backups --rollback can restore and exit")
+				.action(clap::ArgAction::SetTrue)
+				.conflicts_with_all(["dev", "to", "list", "rollback"]),
+		)
+		.arg(
+			Arg::new("allow_downgrade")
+				.long("allow-downgrade")
+				.help("Allow installing a release older than this binary")
+				.action(clap::ArgAction::SetTrue),
+		)
+		.arg(
+			Arg::new("confirm")
+				.long("confirm")
+				.help("Ask before installing, after showing the release notes")
+				.action(clap::ArgAction::SetTrue)
+				.conflicts_with_all(["dev", "list", "list_installed"]),
+		)
+		.arg(
+			Arg::new("yes")
+				.long("yes")
+				.short('y')
+				.help("Do not ask, even if update.confirm is set")
+				.action(clap::ArgAction::SetTrue)
+				.conflicts_with("confirm"),
+		)
+}
+
+fn run(matches: &ArgMatches, ctx: &Context) -> anyhow::Result<()> {
+	let force = matches.get_flag("force");
+
+	if matches.get_flag("list_installed") {
+		return run_list_installed(ctx);
+	}
+
+	let endpoints = Endpoints::from_config(&ctx.config)?;
+	let channel = Channel::from_config(&ctx.config)?;
+	if matches.get_flag("list") {
+		return run_list(&releases(&endpoints)?, channel, ctx);
+	}
+
+	commands::require_platform("Updating eagle", INSTALL_PLATFORMS)?;
+
+	if let Some(dev_path_str) = matches.get_one::<String>("dev") {
+		return run_dev_install(dev_path_str, ctx);
+	}
+
+	if is_dev_exe(&ctx.exe_path) && !force {
+		anyhow::bail!("Refusing to self-update a dev binary. Use --force.");
+	}
+
+	if matches.get_flag("rollback") {
+		let wanted = matches.get_one::<String>("to").map(String::as_str);
+		return run_rollback(wanted, ctx);
+	}
+
+	let current = parse_version(ctx.version).ok_or_else(|| {
+		anyhow::anyhow!("Installed version '{}' is not semver", ctx.version)
+	})?;
+	let wanted = matches.get_one::<String>("to");
+	let releases = releases(&endpoints)?;
+	let release = match wanted {
+		Some(wanted) => find_release(&releases, wanted)?,
+		None => newest(&releases, channel)?,
+	};
+	let target = release.version().ok_or_else(|| {
+		anyhow::anyhow!("Release tag '{}' is not semver", release.tag_name)
+	})?;
+	let latest_version = version_label(release);
+
+	let downgrade = target < current;
+	if target == current || (downgrade && wanted.is_none()) {
+		if downgrade {
+			ui::success(&format!(
+				"Already up to date (v{} is newer than the latest release v{latest_version})",
+				ctx.version
+			));
+		} else {
+			ui::success(&format!("Already up to date (v{})", ctx.version));
+		}
+		ui::result(serde_json::json!({
+			"updated": false,
+			"current_version": ctx.version,
+			"latest_version": latest_version,
+		}));
+		return Ok(());
+	}
+	if downgrade && !matches.get_flag("allow_downgrade") {
+		return Err(Error::new(
+			error::Category::Usage,
+			format!(
+				"v{latest_version} is older than the installed v{}",
+				ctx.version
+			),
+		)
+		.with_hint("Pass --allow-downgrade to install it anyway.")
+		.into());
+	}
+
+	let asset = latest_eagle_asset(release)?;
+	let digest = asset.digest.as_deref().ok_or_else(|| {
+		anyhow::anyhow!("Release asset is missing sha256 digest")
+	})?;
+
+	let changes = if downgrade {
+		Vec::new()
+	} else {
+		notes::between(&releases, &current, &target, channel)
+	};
+	notes::print(&changes);
+
+	let confirm = matches.get_flag("confirm")
+		|| (!matches.get_flag("yes")
+			&& ctx.config.bool("update.confirm")?.unwrap_or(false));
+	if confirm && !confirmed(&latest_version)? {
+		ui::muted("Update cancelled.");
+		ui::result(serde_json::json!({
+			"updated": false,
+			"cancelled": true,
+			"current_version": ctx.version,
+			"latest_version": latest_version,
+		}));
+		return Ok(());
+	}
+
+	let new_path = ctx.exe_dir.join("eagle.new");
+	ui::info(&format!(
+		"{} eagle v{} → v{latest_version}",
+		if downgrade { "Downgrading" } else { "Updating" },
+		ctx.version
+	));
+	ctx.exec
+		.download(&asset.browser_download_url, &new_path, Some(digest))?;
+
+	save_backup(ctx)?;
+	schedule_replace(&new_path, ctx)?;
+	ui::success("Update scheduled. Re-run eagle in a new shell.");
+	ui::result(serde_json::json!({
+		"updated": true,
+		"old_version": ctx.version,
+		"new_version": latest_version,
+		"release_notes": notes::to_json(&changes),
+	}));
+	Ok(())
+}
+
+fn confirmed(version: &str) -> anyhow::Result<bool> {
+	ui::require_interactive("confirmation", "--yes")?;
+	let theme = ui::theme();
+	Ok(Confirm::with_theme(theme.as_ref())
+		.with_prompt(format!("Install eagle v{version}?"))
+		.default(false)
+		.interact()?)
+}
+
+/// The release whose version equals `wanted`.
+fn find_release<'a>(
+	releases: &'a [GithubRelease],
+	wanted: &str,
+) -> anyhow::Result<&'a GithubRelease> {
+	let version = parse_version(wanted).ok_or_else(|| {
+		error::usage(format!(
+			"Invalid version '{wanted}' (expected e.g. 3.4.0 or v3.4.0-beta.1)"
+		))
+	})?;
+
+	let labels: Vec<String> = releases.iter().map(version_label).collect();
+	releases
+		.iter()
+		.find(|r| r.version().as_ref() == Some(&version))
+		.ok_or_else(|| {
+			error::unknown_value(
+				format!("No release v{version} found"),
+				wanted.trim_start_matches('v'),
+				labels.iter().map(String::as_str),
+			)
+		})
+}
+
+fn run_list(
+	releases: &[GithubRelease],
+	channel: Channel,
+	ctx: &Context,
+) -> anyhow::Result<()> {
+	let current = parse_version(ctx.version);
+	let latest = releases.iter().find(|r| channel.includes(r));
+
+	let rows: Vec<serde_json::Value> = releases
+		.iter()
+		.map(|r| {
+			serde_json::json!({
+				"version": version_label(r),
+				"prerelease": r.prerelease,
+				"latest": latest.is_some_and(|l| std::ptr::eq(l, r)),
+				"installed": r.version() == current,
+			})
+		})
+		.collect();
+
+	if !ui::json_enabled() {
+		if rows.is_empty() {
+			ui::muted("No published releases found.");
+		}
+		for row in &rows {
+			let notes: Vec<&str> = ["latest", "prerelease", "installed"]
+				.into_iter()
+				.filter(|key| row[*key] == true)
+				.collect();
+			let version = row["version"].as_str().unwrap_or_default();
+			ui::text(&format!("v{version:<16} {}\n", notes.join(", ")));
+		}
+	}
+	ui::result(serde_json::json!({
+		"channel": match channel {
+			Channel::Stable => "stable",
+			Channel::Prerelease => "prerelease",
+		},
+		"releases": rows,
+	}));
+	Ok(())
+}
+
+fn run_rollback(wanted: Option<&str>, ctx: &Context) -> anyhow::Result<()> {
+	let dir = backups::dir(&ctx.exe_dir);
+	let installed = backups::list(&dir)?;
+	if installed.is_empty() {
+		return Err(Error::new(
+			error::Category::Usage,
+			format!("No backups in {}", dir.display()),
+		)
+		.with_hint(
+			"eagle keeps one each time `eagle update` replaces the binary.",
+		)
+		.into());
+	}
+	let backup = backups::pick(&installed, ctx.version, wanted)?;
+
+	let new_path = ctx.exe_dir.join("eagle.new");
+	ui::info(&format!(
+		"Rolling back eagle v{} → v{}",
+		ctx.version, backup.version
+	));
+	ctx.exec.copy(&backup.path, &new_path)?;
+
+	schedule_replace(&new_path, ctx)?;
+	ui::success("Rollback scheduled. Re-run eagle in a new shell.");
+	ui::result(serde_json::json!({
+		"rolled_back": true,
+		"old_version": ctx.version,
+		"new_version": backup.version,
+		"backup": backup.path,
+	}));
+	Ok(())
+}
+
+fn run_list_installed(ctx: &Context) -> anyhow::Result<()> {
+	let dir = backups::dir(&ctx.exe_dir);
+	let installed = backups::list(&dir)?;
+
+	let rows: Vec<serde_json::Value> = installed
+		.iter()
+		.map(|b| {
+			let saved_at = time::OffsetDateTime::from(b.saved_at)
+				.format(&time::format_description::well_known::Rfc3339)
+				.unwrap_or_default();
+			serde_json::json!({
+				"version": b.version,
+				"path": b.path,
+				"saved_at": saved_at,
+			})
+		})
+		.collect();
+
+	if !ui::json_enabled() {
+		if rows.is_empty() {
+			ui::muted(&format!("No backups in {}", dir.display()));
+		}
+		for row in &rows {
+			ui::text(&format!(
+				"v{:<16} {}\n",
+				row["version"].as_str().unwrap_or_default(),
+				row["saved_at"].as_str().unwrap_or_default()
+			));
+		}
+	}
+	ui::result(serde_json::json!({ "dir": dir, "backups": rows }));
+	Ok(())
+}
+
+fn run_dev_install(dev_path_str: &str, ctx: &Context) -> anyhow::Result<()> {
+	let dev_path = {
+		let p = std::path::PathBuf::from(dev_path_str);
+		if p.is_absolute() {
+			p
+		} else {
+			std::env::current_dir()?.join(p)
+		}
+	};
+
+	if !dev_path.exists() {
+		return Err(Error::new(
+			error::Category::Usage,
+			format!("Dev binary not found: {}", dev_path.display()),
+		)
+		.with_hint(
+			"Build it first with `cargo build --release`, or pass the path explicitly: eagle update --dev <PATH>",
+		)
+		.into());
+	}
+
+	let new_path = ctx.exe_dir.join("eagle.new");
+	ui::info(&format!(
+		"Installing dev build: {} → {}",
+		dev_path.display(),
+		ctx.exe_path.display()
+	));
+	ctx.exec.copy(&dev_path, &new_path)?;
+
+	schedule_replace(&new_path, ctx)?;
+	ui::success("Dev build installed. Re-run eagle in a new shell.");
+	ui::result(serde_json::json!({
+		"updated": true,
+		"old_version": ctx.version,
+		"dev_path": dev_path,
+	}));
+	Ok(())
+}
+
+/// Backs up the running binary before a release replaces it. Rollbacks and
+/// `--dev` installs skip this: backups are named by version only, so while
+/// iterating on dev builds each one would replace the real release backup.
+fn save_backup(ctx: &Context) -> anyhow::Result<()> {
+	let keep = ctx
+		.config
+		.u32("update.keep_backups")?
+		.unwrap_or(backups::DEFAULT_KEEP);
+	if let Some(backup) = backups::save(
+		ctx.exec.as_ref(),
+		&ctx.exe_path,
+		&backups::dir(&ctx.exe_dir),
+		ctx.version,
+		keep,
+	)? {
+		ui::muted(&format!("Kept a backup at {}", backup.display()));
+	}
+	Ok(())
+}
+
+fn schedule_replace(
+	new_path: &std::path::Path,
+	ctx: &Context,
+) -> anyhow::Result<()> {
+	let pid = std::process::id();
+	let exe_path =
+		util::escape_sh_single_quoted(&ctx.exe_path.to_string_lossy());
+	let new_path_s = util::escape_sh_single_quoted(&new_path.to_string_lossy());
+
+	// Wait for the current process to exit, then atomically replace the binary
+	// and ensure it remains executable.
+	let script = format!(
+		"while kill -0 {pid} 2>/dev/null; do sleep 0.1; done; \
+mv -f '{new_path_s}' '{exe_path}'; chmod +x '{exe_path}'"
+	);
+
+	ctx.exec.spawn_shell_background(&script)
+}
+
+#[doc(hidden)]
+pub fn is_dev_exe(path: &Path) -> bool {
+	let s = path.to_string_lossy().to_lowercase();
+	s.contains("/target/debug/") || s.contains("/target/release/")
+}
+
+inventory::submit! {
+	CommandSpec {
+		name: "update",
+		command: build,
+		run,
+		examples: &[
+			Example {
+				command: "eagle update",
+				about: "Install the latest release over this binary",
+			},
+			Example {
+				command: "eagle update --confirm",
+				about: "Read what changed, then decide whether to install",
+			},
+			Example {
+				command: "eagle update --rollback",
+				about: "Go back to the binary the last update replaced",
+			},
+			Example {
+				command: "eagle update --dev",
+				about: "Install ./target/debug/eagle instead",
+			},
+		],
+		category: Category::SelfManagement,
+		platforms: &[],
+		tools: &[],
+		stability: Stability::Stable,
+	}
+}
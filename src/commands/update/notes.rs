@@ -0,0 +1,178 @@
+//! Release notes between two versions, read from the bodies of GitHub
+//! releases. `eagle changelog` prints them, and so does `update` before it
+//! installs a newer release.
+
+use crossterm::style::Stylize;
+use semver::Version;
+
+use super::{Channel, GithubRelease};
+use crate::net::Endpoints;
+use crate::ui;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReleaseNotes {
+	pub version: Version,
+	/// The release name, when it says more than the tag.
+	pub title: Option<String>,
+	/// `YYYY-MM-DD` the release was published on.
+	pub date: Option<String>,
+	/// Markdown, as written on the release page.
+	pub body: String,
+}
+
+/// Notes of the releases after `since` up to and including `until`, newest
+/// first. Prereleases are skipped unless `channel` includes them or one is
+/// `until` itself.
+pub(super) fn between(
+	releases: &[GithubRelease],
+	since: &Version,
+	until: &Version,
+	channel: Channel,
+) -> Vec<ReleaseNotes> {
+	releases
+		.iter()
+		.filter_map(|r| Some((r, r.version()?)))
+		.filter(|(r, v)| {
+			v > since && v <= until && (channel.includes(r) || v == until)
+		})
+		.map(|(r, version)| ReleaseNotes {
+			title: r
+				.name
+				.as_deref()
+				.map(str::trim)
+				.filter(|n| {
+					!n.is_empty()
+						&& super::parse_version(n).as_ref() != Some(&version)
+				})
+				.map(str::to_string),
+			date: r
+				.published_at
+				.as_deref()
+				.and_then(|p| p.get(..10))
+				.map(str::to_string),
+			body: r.body.clone().unwrap_or_default(),
+			version,
+		})
+		.collect()
+}
+
+/// Notes of the releases after `since` up to the newest one on `channel`.
+pub(crate) fn since(
+	endpoints: &Endpoints,
+	channel: Channel,
+	since: &Version,
+) -> anyhow::Result<Vec<ReleaseNotes>> {
+	let releases = super::releases(endpoints)?;
+	let Some(newest) = releases
+		.iter()
+		.find(|r| channel.includes(r))
+		.and_then(GithubRelease::version)
+	else {
+		return Ok(Vec::new());
+	};
+	Ok(between(&releases, since, &newest, channel))
+}
+
+/// Renders `notes` for the terminal: a heading per release, then its body
+/// with Markdown headings and list markers simplified.
+#[doc(hidden)]
+pub fn render(notes: &[ReleaseNotes], colors: bool) -> String {
+	let mut out = String::new();
+	for (i, release) in notes.iter().enumerate() {
+		if i > 0 {
+			out.push('\n');
+		}
+
+		let mut heading = format!("v{}", release.version);
+		if let Some(title) = &release.title {
+			heading.push_str(&format!(" – {title}"));
+		}
+		if colors {
+			heading = heading.bold().to_string();
+		}
+		if let Some(date) = &release.date {
+			let date = format!("({date})");
+			let date = if colors {
+				date.dark_grey().to_string()
+			} else {
+				date
+			};
+			heading.push_str(&format!(" {date}"));
+		}
+		out.push_str(&heading);
+		out.push('\n');
+
+		let body = render_body(&release.body, colors);
+		if body.is_empty() {
+			out.push_str("  No release notes.\n");
+		}
+		for line in body {
+			if !line.is_empty() {
+				out.push_str("  ");
+			}
+			out.push_str(&line);
+			out.push('\n');
+		}
+	}
+	out
+}
+
+fn render_body(body: &str, colors: bool) -> Vec<String> {
+	let mut lines: Vec<String> = Vec::new();
+	for line in body.lines().map(str::trim_end) {
+		if line.is_empty() {
+			if lines.last().is_some_and(|l| !l.is_empty()) {
+				lines.push(String::new());
+			}
+			continue;
+		}
+
+		let text = line.trim_start();
+		let indent = line.strip_suffix(text).unwrap_or_default();
+		let rendered = if let Some(heading) = markdown_heading(text) {
+			if colors {
+				heading.bold().to_string()
+			} else {
+				heading.to_string()
+			}
+		} else if let Some(item) = ["- ", "* ", "+ "]
+			.iter()
+			.find_map(|marker| text.strip_prefix(marker))
+		{
+			format!("{indent}• {item}")
+		} else {
+			format!("{indent}{text}")
+		};
+		lines.push(rendered);
+	}
+	while lines.last().is_some_and(String::is_empty) {
+		lines.pop();
+	}
+	lines
+}
+
+fn markdown_heading(text: &str) -> Option<&str> {
+	let rest = text.trim_start_matches('#');
+	let level = text.len() - rest.len();
+	((1..=6).contains(&level) && rest.starts_with(' ')).then(|| rest.trim())
+}
+
+/// Prints `notes` on stdout unless `-q` is given; in JSON mode they only go
+/// into the result.
+pub fn print(notes: &[ReleaseNotes]) {
+	ui::text(&render(notes, ui::stdout_colors()));
+}
+
+pub fn to_json(notes: &[ReleaseNotes]) -> serde_json::Value {
+	notes
+		.iter()
+		.map(|n| {
+			serde_json::json!({
+				"version": n.version.to_string(),
+				"title": n.title,
+				"date": n.date,
+				"body": n.body,
+			})
+		})
+		.collect()
+}
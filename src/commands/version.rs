@@ -1,18 +1,115 @@
-use clap::{ArgMatches, Command};
+use clap::{Arg, ArgAction, ArgMatches, Command};
 
-use crate::commands::CommandSpec;
+use crate::build_info::BuildInfo;
+use crate::commands::update::{self, Channel};
+use crate::commands::{Category, CommandSpec, Example, Stability};
 use crate::context::Context;
+use crate::net::Endpoints;
+use crate::notice;
 use crate::ui;
 
 fn build() -> Command {
 	Command::new("version")
 		.about("Show the current version")
 		.alias("v")
+		.arg(
+			Arg::new("check")
+				.long("check")
+				.help("Also ask GitHub for the latest release")
+				.action(ArgAction::SetTrue),
+		)
+		.arg(
+			Arg::new("build")
+				.long("build")
+				.help("Also show commit, build time, target and toolchain")
+				.action(ArgAction::SetTrue),
+		)
 }
 
-fn run(_: &ArgMatches, ctx: &Context) -> anyhow::Result<()> {
+fn run(matches: &ArgMatches, ctx: &Context) -> anyhow::Result<()> {
+	if matches.get_flag("check") {
+		return run_check(ctx);
+	}
+
 	ui::success(&format!("eagle v{}", ctx.version_string()));
 	ui::muted(ctx.repo_url);
+	if matches.get_flag("build") {
+		print_build(&ctx.build);
+	}
+	ui::result(serde_json::json!({
+		"version": ctx.version_string(),
+		"repo_url": ctx.repo_url,
+		"build": build_json(&ctx.build),
+	}));
+	Ok(())
+}
+
+fn print_build(build: &BuildInfo) {
+	let features = build.feature_list();
+	let features = if features.is_empty() {
+		"none".to_string()
+	} else {
+		features.join(", ")
+	};
+	for (label, value) in [
+		("commit", build.commit_label()),
+		("built", build.built_at()),
+		("target", build.target.to_string()),
+		("profile", build.profile.to_string()),
+		("rustc", build.rustc.to_string()),
+		("features", features),
+	] {
+		ui::muted(&format!("{label:<9} {value}"));
+	}
+}
+
+fn build_json(build: &BuildInfo) -> serde_json::Value {
+	serde_json::json!({
+		"commit": build.commit,
+		"dirty": build.dirty,
+		"built_at": build.built_at(),
+		"target": build.target,
+		"profile": build.profile,
+		"rustc": build.rustc,
+		"features": build.feature_list(),
+	})
+}
+
+fn run_check(ctx: &Context) -> anyhow::Result<()> {
+	let latest = update::latest_version(
+		&Endpoints::from_config(&ctx.config)?,
+		Channel::from_config(&ctx.config)?,
+	)?;
+	let outdated = update::is_newer(&latest, ctx.version);
+
+	// Saves the passive notice its own request today.
+	if let Ok(path) = notice::cache_path(&ctx.paths) {
+		let _ = notice::write_cache(
+			&path,
+			&notice::Cache {
+				checked_at: notice::now(),
+				latest_version: Some(latest.clone()),
+			},
+		);
+	}
+
+	if outdated {
+		ui::warning(&format!(
+			"eagle v{} is outdated; latest is v{latest}",
+			ctx.version_string()
+		));
+		ui::muted("Run `eagle update` to install it.");
+	} else {
+		ui::success(&format!(
+			"eagle v{} is up to date (latest: v{latest})",
+			ctx.version_string()
+		));
+	}
+	ui::result(serde_json::json!({
+		"version": ctx.version_string(),
+		"latest_version": latest,
+		"update_available": outdated,
+	}));
 	Ok(())
 }
 
@@ -21,5 +118,19 @@ inventory::submit! {
 		name: "version",
 		command: build,
 		run,
+		examples: &[
+			Example {
+				command: "eagle version --check",
+				about: "Compare with the latest release without installing it",
+			},
+			Example {
+				command: "eagle version --build",
+				about: "Show the commit, target and compiler for a bug report",
+			},
+		],
+		category: Category::SelfManagement,
+		platforms: &[],
+		tools: &[],
+		stability: Stability::Stable,
 	}
 }
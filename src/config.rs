@@ -0,0 +1,483 @@
+//! Persistent user configuration.
+//!
+//! Values live in a TOML file under the user config dir (override the location
+//! with `EAGLE_CONFIG`). Commands resolve their defaults with the precedence
+//! CLI flag > env var > config file > built-in default. The getters here
+//! cover the env var (each [`KeySpec::env`]) and the file; the CLI flag and
+//! the built-in default are up to the command.
+
+use std::path::{Path, PathBuf};
+
+use crate::error::{Category, Error};
+use crate::exec::{Executor, System};
+use crate::paths::Paths;
+
+/// The type a config value must have.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueKind {
+	String,
+	Path,
+	U16,
+	U32,
+	Bool,
+}
+
+/// A known config key. `*` in `key` matches exactly one dotted segment.
+#[derive(Debug, Clone, Copy)]
+pub struct KeySpec {
+	pub key: &'static str,
+	pub env: Option<&'static str>,
+	pub kind: ValueKind,
+	pub description: &'static str,
+}
+
+pub const KEYS: &[KeySpec] = &[
+	KeySpec {
+		key: "create.root",
+		env: Some("EAGLE_CREATE_ROOT"),
+		kind: ValueKind::Path,
+		description: "Base path for `eagle create` projects",
+	},
+	KeySpec {
+		key: "templates.*.repo",
+		env: None,
+		kind: ValueKind::String,
+		description: "Git URL of a custom `eagle create` template",
+	},
+	KeySpec {
+		key: "templates.*.dir",
+		env: None,
+		kind: ValueKind::String,
+		description: "Subfolder of the create root used by a custom template",
+	},
+	KeySpec {
+		key: "alias.*",
+		env: None,
+		kind: ValueKind::String,
+		description: "Command line a user alias expands to, e.g. \"minecraft create --type paper\"",
+	},
+	KeySpec {
+		key: "minecraft.servers_root",
+		env: Some("EAGLE_MINECRAFT_SERVERS_ROOT"),
+		kind: ValueKind::Path,
+		description: "Folder that holds Minecraft servers",
+	},
+	KeySpec {
+		key: "minecraft.ram_mb",
+		env: Some("EAGLE_MINECRAFT_RAM_MB"),
+		kind: ValueKind::U32,
+		description: "RAM in MB for `eagle minecraft`",
+	},
+	KeySpec {
+		key: "minecraft.port",
+		env: Some("EAGLE_MINECRAFT_PORT"),
+		kind: ValueKind::U16,
+		description: "Default port for `eagle minecraft create`",
+	},
+	KeySpec {
+		key: "minecraft.motd",
+		env: Some("EAGLE_MINECRAFT_MOTD"),
+		kind: ValueKind::String,
+		description: "Default motd for `eagle minecraft create`",
+	},
+	KeySpec {
+		key: "eaglecord.dir",
+		env: Some("EAGLE_EAGLECORD_DIR"),
+		kind: ValueKind::Path,
+		description: "Folder that holds the EagleCord clone",
+	},
+	KeySpec {
+		key: "log.path",
+		env: Some("EAGLE_RUN_LOG"),
+		kind: ValueKind::Path,
+		description: "Run log file read by `eagle logs`",
+	},
+	KeySpec {
+		key: "update.notify",
+		env: Some("EAGLE_UPDATE_NOTIFY"),
+		kind: ValueKind::Bool,
+		description: "Mention a newer release after commands (checked at most daily; true also in dev builds and pipes)",
+	},
+	KeySpec {
+		key: "update.channel",
+		env: Some("EAGLE_UPDATE_CHANNEL"),
+		kind: ValueKind::String,
+		description: "Releases `eagle update` installs: stable or prerelease",
+	},
+	KeySpec {
+		key: "update.confirm",
+		env: Some("EAGLE_UPDATE_CONFIRM"),
+		kind: ValueKind::Bool,
+		description: "Ask before `eagle update` installs a release (skip once with --yes)",
+	},
+	KeySpec {
+		key: "update.keep_backups",
+		env: Some("EAGLE_UPDATE_KEEP_BACKUPS"),
+		kind: ValueKind::U32,
+		description: "How many replaced binaries `eagle update` keeps for --rollback (0 turns backups off)",
+	},
+	KeySpec {
+		key: "endpoints.github_api",
+		env: Some("EAGLE_GITHUB_API"),
+		kind: ValueKind::String,
+		description: "Base URL of the GitHub API used by `eagle update`",
+	},
+	KeySpec {
+		key: "endpoints.paper_api",
+		env: Some("EAGLE_PAPER_API"),
+		kind: ValueKind::String,
+		description: "Base URL of the PaperMC Fill API",
+	},
+	KeySpec {
+		key: "endpoints.fabric_meta",
+		env: Some("EAGLE_FABRIC_META"),
+		kind: ValueKind::String,
+		description: "Base URL of the Fabric meta API",
+	},
+];
+
+/// Returns the [`KeySpec`] matching a dotted key, if any.
+pub fn key_spec(key: &str) -> Option<&'static KeySpec> {
+	KEYS.iter().find(|spec| key_matches(spec.key, key))
+}
+
+fn key_matches(pattern: &str, key: &str) -> bool {
+	let pattern: Vec<&str> = pattern.split('.').collect();
+	let key: Vec<&str> = key.split('.').collect();
+
+	pattern.len() == key.len()
+		&& pattern
+			.iter()
+			.zip(&key)
+			.all(|(p, k)| !k.is_empty() && (*p == "*" || p == k))
+}
+
+#[derive(Debug, Clone, Default)]
+/// The parsed config file together with the path it was loaded from.
+pub struct Config {
+	path: PathBuf,
+	table: toml::Table,
+	/// Why the file at `path` was ignored, if it could not be loaded.
+	load_error: Option<String>,
+}
+
+impl Config {
+	/// Returns the config file location: `EAGLE_CONFIG` if set, otherwise
+	/// `config.toml` in `EAGLE_HOME` or the platform config dir.
+	pub fn default_path() -> anyhow::Result<PathBuf> {
+		if let Ok(value) = std::env::var("EAGLE_CONFIG") {
+			let value = value.trim();
+			if value.is_empty() {
+				anyhow::bail!("EAGLE_CONFIG is set but empty");
+			}
+			return Ok(PathBuf::from(value));
+		}
+
+		Paths::from_env().config_file()
+	}
+
+	/// Loads the config from [`Config::default_path`]. A file that cannot
+	/// be read or parsed is ignored so every command, `config edit`
+	/// included, still runs; [`Config::load_error`] says why.
+	pub fn load_default() -> Self {
+		let path = match Self::default_path() {
+			Ok(path) => path,
+			Err(err) => return Self::ignored(PathBuf::new(), &err),
+		};
+		Self::load(&path).unwrap_or_else(|err| Self::ignored(path, &err))
+	}
+
+	fn ignored(path: PathBuf, err: &anyhow::Error) -> Self {
+		Self {
+			path,
+			table: toml::Table::new(),
+			load_error: Some(format!("{err:#}")),
+		}
+	}
+
+	/// Why [`Config::load_default`] fell back to an empty config.
+	pub fn load_error(&self) -> Option<&str> {
+		self.load_error.as_deref()
+	}
+
+	/// Loads the config at `path`. A missing file yields an empty config.
+	pub fn load(path: &Path) -> anyhow::Result<Self> {
+		let text = match std::fs::read_to_string(path) {
+			Ok(text) => text,
+			Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+				String::new()
+			}
+			Err(err) => return Err(err.into()),
+		};
+
+		let table = text.parse::<toml::Table>().map_err(|err| {
+			anyhow::anyhow!("Invalid config file {}: {err}", path.display())
+		})?;
+
+		Ok(Self {
+			path: path.to_path_buf(),
+			table,
+			load_error: None,
+		})
+	}
+
+	pub fn path(&self) -> &Path {
+		&self.path
+	}
+
+	/// Writes the config back to [`Config::path`], creating parent dirs.
+	pub fn save(&self) -> anyhow::Result<()> {
+		self.save_with(&System::default())
+	}
+
+	/// Like [`Config::save`], but through `exec` so `--dry-run` applies.
+	/// Refuses to overwrite a file that was ignored on load.
+	pub fn save_with(&self, exec: &dyn Executor) -> anyhow::Result<()> {
+		if let Some(err) = &self.load_error {
+			return Err(Error::new(
+				Category::General,
+				format!("Not overwriting the config file: {err}"),
+			)
+			.with_hint("Fix it with `eagle config edit`.")
+			.into());
+		}
+		if let Some(parent) = self.path.parent() {
+			exec.create_dir_all(parent)?;
+		}
+		let text = toml::to_string_pretty(&self.table)?;
+		exec.write(&self.path, text.as_bytes())
+	}
+
+	/// Returns the raw value stored in the file for a dotted key.
+	pub fn get(&self, key: &str) -> Option<&toml::Value> {
+		let mut parts = key.split('.');
+		let mut value = self.table.get(parts.next()?)?;
+		for part in parts {
+			value = value.as_table()?.get(part)?;
+		}
+		Some(value)
+	}
+
+	/// Validates `raw` against the key's [`ValueKind`] and stores it.
+	pub fn set(&mut self, key: &str, raw: &str) -> anyhow::Result<()> {
+		let spec = key_spec(key).ok_or_else(|| unknown_key(key))?;
+		let value = parse_value(spec, raw)?;
+
+		let segments: Vec<&str> = key.split('.').collect();
+		let (last, parents) = segments
+			.split_last()
+			.ok_or_else(|| anyhow::anyhow!("Empty config key"))?;
+
+		let mut table = &mut self.table;
+		for part in parents {
+			let entry = table
+				.entry(part.to_string())
+				.or_insert_with(|| toml::Value::Table(toml::Table::new()));
+			table = entry.as_table_mut().ok_or_else(|| {
+				anyhow::anyhow!("Config key '{part}' is not a table")
+			})?;
+		}
+		table.insert(last.to_string(), value);
+		Ok(())
+	}
+
+	/// Removes a dotted key, pruning tables left empty. Returns whether
+	/// anything was removed.
+	pub fn unset(&mut self, key: &str) -> bool {
+		let segments: Vec<&str> = key.split('.').collect();
+		remove_path(&mut self.table, &segments)
+	}
+
+	/// Returns every scalar value in the file as `(dotted.key, value)`.
+	pub fn entries(&self) -> Vec<(String, toml::Value)> {
+		let mut out = Vec::new();
+		flatten("", &self.table, &mut out);
+		out
+	}
+
+	/// Resolves a string key: env var first, then the config file.
+	pub fn string(&self, key: &str) -> anyhow::Result<Option<String>> {
+		if let Some(value) = self.env_value(key)? {
+			return Ok(Some(value));
+		}
+
+		match self.get(key) {
+			None => Ok(None),
+			Some(toml::Value::String(s)) => Ok(Some(s.clone())),
+			Some(other) => anyhow::bail!(
+				"Config key '{key}' must be a string, got {}",
+				other.type_str()
+			),
+		}
+	}
+
+	/// Resolves a path key like [`Config::string`], expanding a leading `~`.
+	pub fn path_value(&self, key: &str) -> anyhow::Result<Option<PathBuf>> {
+		Ok(self.string(key)?.map(|s| expand_tilde(&s)))
+	}
+
+	/// Resolves an unsigned integer key: env var first, then the config file.
+	pub fn u32(&self, key: &str) -> anyhow::Result<Option<u32>> {
+		self.integer(key)
+	}
+
+	/// Resolves a port-sized integer key: env var first, then the config file.
+	pub fn u16(&self, key: &str) -> anyhow::Result<Option<u16>> {
+		self.integer(key)
+	}
+
+	/// Resolves a boolean key: env var first, then the config file.
+	pub fn bool(&self, key: &str) -> anyhow::Result<Option<bool>> {
+		if let Some(value) = self.env_value(key)? {
+			return parse_bool(&value).map(Some).ok_or_else(|| {
+				anyhow::anyhow!("Invalid value for {key}: {value}")
+			});
+		}
+
+		match self.get(key) {
+			None => Ok(None),
+			Some(toml::Value::Boolean(b)) => Ok(Some(*b)),
+			Some(other) => anyhow::bail!(
+				"Config key '{key}' must be a boolean, got {}",
+				other.type_str()
+			),
+		}
+	}
+
+	fn integer<T>(&self, key: &str) -> anyhow::Result<Option<T>>
+	where
+		T: TryFrom<i64> + std::str::FromStr,
+	{
+		if let Some(value) = self.env_value(key)? {
+			let parsed = value.parse::<T>().map_err(|_| {
+				anyhow::anyhow!("Invalid value for {key}: {value}")
+			})?;
+			return Ok(Some(parsed));
+		}
+
+		match self.get(key) {
+			None => Ok(None),
+			Some(toml::Value::Integer(n)) => {
+				T::try_from(*n).map(Some).map_err(|_| {
+					anyhow::anyhow!("Config key '{key}' out of range")
+				})
+			}
+			Some(other) => anyhow::bail!(
+				"Config key '{key}' must be an integer, got {}",
+				other.type_str()
+			),
+		}
+	}
+
+	fn env_value(&self, key: &str) -> anyhow::Result<Option<String>> {
+		let Some(env) = key_spec(key).and_then(|spec| spec.env) else {
+			return Ok(None);
+		};
+
+		match std::env::var(env) {
+			Ok(value) => {
+				let value = value.trim();
+				if value.is_empty() {
+					anyhow::bail!("{env} is set but empty");
+				}
+				Ok(Some(value.to_string()))
+			}
+			Err(_) => Ok(None),
+		}
+	}
+}
+
+fn unknown_key(key: &str) -> anyhow::Error {
+	let known = KEYS.iter().map(|s| s.key).collect::<Vec<_>>().join(", ");
+	anyhow::anyhow!("Unknown config key '{key}' (known keys: {known})")
+}
+
+fn parse_value(spec: &KeySpec, raw: &str) -> anyhow::Result<toml::Value> {
+	let key = spec.key;
+	match spec.kind {
+		ValueKind::String | ValueKind::Path => {
+			if raw.trim().is_empty() {
+				anyhow::bail!("Value for '{key}' must not be empty");
+			}
+			Ok(toml::Value::String(raw.to_string()))
+		}
+		ValueKind::U16 => raw
+			.trim()
+			.parse::<u16>()
+			.map(|n| toml::Value::Integer(i64::from(n)))
+			.map_err(|_| anyhow::anyhow!("'{key}' expects a port (0-65535)")),
+		ValueKind::U32 => raw
+			.trim()
+			.parse::<u32>()
+			.map(|n| toml::Value::Integer(i64::from(n)))
+			.map_err(|_| {
+				anyhow::anyhow!("'{key}' expects a non-negative integer")
+			}),
+		ValueKind::Bool => parse_bool(raw)
+			.map(toml::Value::Boolean)
+			.ok_or_else(|| anyhow::anyhow!("'{key}' expects true or false")),
+	}
+}
+
+fn parse_bool(raw: &str) -> Option<bool> {
+	match raw.trim().to_ascii_lowercase().as_str() {
+		"true" | "yes" | "on" | "1" => Some(true),
+		"false" | "no" | "off" | "0" => Some(false),
+		_ => None,
+	}
+}
+
+fn remove_path(table: &mut toml::Table, segments: &[&str]) -> bool {
+	match segments {
+		[] => false,
+		[last] => table.remove(*last).is_some(),
+		[first, rest @ ..] => {
+			let Some(child) =
+				table.get_mut(*first).and_then(|v| v.as_table_mut())
+			else {
+				return false;
+			};
+			let removed = remove_path(child, rest);
+			if removed && child.is_empty() {
+				table.remove(*first);
+			}
+			removed
+		}
+	}
+}
+
+fn flatten(
+	prefix: &str,
+	table: &toml::Table,
+	out: &mut Vec<(String, toml::Value)>,
+) {
+	for (key, value) in table {
+		let full = if prefix.is_empty() {
+			key.clone()
+		} else {
+			format!("{prefix}.{key}")
+		};
+		match value {
+			toml::Value::Table(child) => flatten(&full, child, out),
+			other => out.push((full, other.clone())),
+		}
+	}
+}
+
+#[doc(hidden)]
+pub fn expand_tilde(value: &str) -> PathBuf {
+	let home =
+		|| directories::UserDirs::new().map(|u| u.home_dir().to_path_buf());
+
+	if value == "~" {
+		if let Some(home) = home() {
+			return home;
+		}
+	} else if let Some(rest) = value.strip_prefix("~/")
+		&& let Some(home) = home()
+	{
+		return home.join(rest);
+	}
+
+	PathBuf::from(value)
+}
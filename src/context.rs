@@ -1,4 +1,11 @@
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::build_info::BuildInfo;
+use crate::config::Config;
+use crate::exec::{self, Executor};
+use crate::paths::Paths;
+use crate::process::{self, ProcessRunner};
 
 #[derive(Debug, Clone)]
 /// Runtime information available to all commands.
@@ -7,29 +14,58 @@ pub struct Context {
 	pub exe_dir: PathBuf,
 	pub version: &'static str,
 	pub repo_url: &'static str,
+	/// Commit, target, compiler and features this binary was built with.
+	pub build: BuildInfo,
 	/// Whether the CLI was invoked with the global `--dev` flag.
 	pub dev_mode: bool,
+	/// User settings loaded from the config file.
+	pub config: Config,
+	/// Default locations of eagle-managed state, rooted at `EAGLE_HOME`
+	/// when set.
+	pub paths: Paths,
+	/// Performs filesystem changes, subprocesses and downloads; swapped for
+	/// [`exec::DryRun`] by `--dry-run`.
+	pub exec: Arc<dyn Executor>,
+	/// Finds and starts external tools; [`Context::exec`] starts processes
+	/// through it too.
+	pub processes: Arc<dyn ProcessRunner>,
 }
 
 impl Context {
-	/// Constructs a [`Context`] by discovering the current executable path.
-	/// Dev mode is enabled automatically for debug builds.
+	/// Constructs a [`Context`] by discovering the current executable path
+	/// and loading the user config. Dev mode is enabled automatically for
+	/// debug builds.
 	pub fn new() -> anyhow::Result<Self> {
 		let exe_path = std::env::current_exe()?;
 		let exe_dir = exe_path
 			.parent()
 			.map(Path::to_path_buf)
 			.unwrap_or_else(|| PathBuf::from("."));
+		let config = Config::load_default();
+		let processes: Arc<dyn ProcessRunner> = Arc::new(process::Native);
 
 		Ok(Self {
 			exe_path,
 			exe_dir,
 			version: env!("CARGO_PKG_VERSION"),
 			repo_url: "https://github.com/prodbyeagle/cli",
+			build: BuildInfo::CURRENT,
 			dev_mode: cfg!(debug_assertions),
+			config,
+			paths: Paths::from_env(),
+			exec: Arc::new(exec::System::new(processes.clone())),
+			processes,
 		})
 	}
 
+	/// Replaces the process runner, for both tool lookups and the
+	/// executor.
+	pub fn with_processes(mut self, processes: Arc<dyn ProcessRunner>) -> Self {
+		self.exec = Arc::new(exec::System::new(processes.clone()));
+		self.processes = processes;
+		self
+	}
+
 	/// Returns the version string, appending `-dev` when in dev mode.
 	pub fn version_string(&self) -> String {
 		if self.dev_mode {
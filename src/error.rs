@@ -0,0 +1,232 @@
+//! Error categories with their exit codes and remediation hints.
+//!
+//! Commands keep returning `anyhow::Result`; failures that scripts should be
+//! able to tell apart are raised as an [`Error`] (see the constructors below)
+//! and `main` maps the chain to a [`Category`] via [`category`].
+
+use std::fmt;
+use std::process::ExitStatus;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+	/// Anything not categorized below.
+	General,
+	/// Invalid arguments, flags or input values.
+	Usage,
+	/// A required program (git, bun, java, ...) is not on `PATH`.
+	MissingTool,
+	/// HTTP failures, timeouts and unexpected responses.
+	Network,
+	/// A download did not match its expected checksum.
+	Integrity,
+	/// Filesystem errors.
+	Io,
+	/// The user aborted a prompt.
+	Cancelled,
+	/// A child process exited unsuccessfully.
+	ExternalProcess,
+}
+
+impl Category {
+	pub fn exit_code(self) -> i32 {
+		match self {
+			Self::General => 1,
+			Self::Usage => 2,
+			Self::MissingTool => 3,
+			Self::Network => 4,
+			Self::Integrity => 5,
+			Self::Io => 6,
+			Self::ExternalProcess => 7,
+			Self::Cancelled => 130,
+		}
+	}
+
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::General => "general",
+			Self::Usage => "usage",
+			Self::MissingTool => "missing-tool",
+			Self::Network => "network",
+			Self::Integrity => "integrity",
+			Self::Io => "io",
+			Self::Cancelled => "cancelled",
+			Self::ExternalProcess => "external-process-failed",
+		}
+	}
+
+	fn default_hint(self) -> Option<&'static str> {
+		match self {
+			Self::MissingTool => {
+				Some("Install it and make sure it is on your PATH.")
+			}
+			Self::Network => Some(
+				"Check your internet connection and retry; -vv shows every request.",
+			),
+			Self::Integrity => Some(
+				"The partial download was discarded. Retry; if it keeps failing the upstream file changed.",
+			),
+			Self::Io => {
+				Some("Check that the path exists and that you can write to it.")
+			}
+			Self::ExternalProcess => {
+				Some("See the command's own output above for details.")
+			}
+			Self::General | Self::Usage | Self::Cancelled => None,
+		}
+	}
+}
+
+/// A categorized error, optionally carrying a hint more specific than the
+/// category default.
+#[derive(Debug)]
+pub struct Error {
+	category: Category,
+	message: String,
+	hint: Option<String>,
+}
+
+impl Error {
+	pub fn new(category: Category, message: impl Into<String>) -> Self {
+		Self {
+			category,
+			message: message.into(),
+			hint: None,
+		}
+	}
+
+	pub fn with_hint(mut self, hint: impl Into<String>) -> Self {
+		self.hint = Some(hint.into());
+		self
+	}
+
+	pub fn category(&self) -> Category {
+		self.category
+	}
+
+	pub fn hint(&self) -> Option<&str> {
+		self.hint.as_deref()
+	}
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(&self.message)
+	}
+}
+
+impl std::error::Error for Error {}
+
+pub fn usage(message: impl Into<String>) -> anyhow::Error {
+	Error::new(Category::Usage, message).into()
+}
+
+/// A usage error for a value that is not one of `candidates`, hinting the
+/// one the user most likely meant.
+pub fn unknown_value<'a>(
+	message: impl Into<String>,
+	input: &str,
+	candidates: impl IntoIterator<Item = &'a str>,
+) -> anyhow::Error {
+	let err = Error::new(Category::Usage, message);
+	match crate::util::closest(input, candidates) {
+		Some(s) => err.with_hint(format!("Did you mean '{s}'?")).into(),
+		None => err.into(),
+	}
+}
+
+/// `tool` is not on `PATH`; `hint` says how to install it.
+pub fn missing_tool(tool: &str, hint: &str) -> anyhow::Error {
+	Error::new(Category::MissingTool, format!("{tool} not found in PATH"))
+		.with_hint(hint)
+		.into()
+}
+
+pub fn network(message: impl Into<String>) -> anyhow::Error {
+	Error::new(Category::Network, message).into()
+}
+
+pub fn integrity(message: impl Into<String>) -> anyhow::Error {
+	Error::new(Category::Integrity, message).into()
+}
+
+pub fn cancelled() -> anyhow::Error {
+	Error::new(Category::Cancelled, "Cancelled").into()
+}
+
+/// A prompt was needed while running non-interactively.
+pub fn prompt_unavailable(what: &str, instead: &str) -> anyhow::Error {
+	Error::new(
+		Category::Usage,
+		format!(
+			"Cannot ask for {what} in non-interactive mode; pass {instead}"
+		),
+	)
+	.with_hint(
+		"Prompts are off with --non-interactive or when stdin is not a terminal",
+	)
+	.into()
+}
+
+/// `program` ran but exited unsuccessfully.
+pub fn process_failed(program: &str, status: ExitStatus) -> anyhow::Error {
+	Error::new(
+		Category::ExternalProcess,
+		format!("{program} exited with: {status}"),
+	)
+	.into()
+}
+
+/// Returns the category of the first categorizable error in the chain.
+pub fn category(err: &anyhow::Error) -> Category {
+	err.chain()
+		.find_map(category_of)
+		.unwrap_or(Category::General)
+}
+
+fn category_of(cause: &(dyn std::error::Error + 'static)) -> Option<Category> {
+	if let Some(err) = cause.downcast_ref::<Error>() {
+		return Some(err.category);
+	}
+	if cause.downcast_ref::<clap::Error>().is_some() {
+		return Some(Category::Usage);
+	}
+	if cause.downcast_ref::<ureq::Error>().is_some() {
+		return Some(Category::Network);
+	}
+	if let Some(dialoguer::Error::IO(err)) =
+		cause.downcast_ref::<dialoguer::Error>()
+	{
+		return Some(io_category(err));
+	}
+	if let Some(err) = cause.downcast_ref::<std::io::Error>() {
+		return Some(io_category(err));
+	}
+	None
+}
+
+fn io_category(err: &std::io::Error) -> Category {
+	use std::io::ErrorKind;
+
+	match err.kind() {
+		// Ctrl-C inside a dialoguer prompt surfaces as an interrupted read.
+		ErrorKind::Interrupted => Category::Cancelled,
+		// Socket errors while streaming a response body.
+		ErrorKind::TimedOut
+		| ErrorKind::ConnectionReset
+		| ErrorKind::ConnectionAborted => Category::Network,
+		_ => Category::Io,
+	}
+}
+
+/// Returns the most specific hint for `err`: one attached to an [`Error`] in
+/// the chain, otherwise the category default.
+pub fn hint(err: &anyhow::Error) -> Option<String> {
+	let attached = err
+		.chain()
+		.filter_map(|cause| cause.downcast_ref::<Error>())
+		.find_map(Error::hint);
+
+	attached
+		.or_else(|| category(err).default_hint())
+		.map(str::to_string)
+}
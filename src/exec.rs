@@ -0,0 +1,239 @@
+//! Side effects behind one interface.
+//!
+//! Commands make filesystem changes, spawn processes and download files
+//! through the [`Executor`] on [`Context`](crate::context::Context) instead of
+//! calling `std::fs`, `process` or `net` directly. [`System`] performs them;
+//! [`DryRun`] (`--dry-run`) only prints what would happen, except for
+//! read-only captures.
+
+use std::fmt;
+use std::path::Path;
+use std::process::ExitStatus;
+use std::sync::Arc;
+
+use crate::net;
+use crate::process::{Native, ProcessRunner};
+use crate::ui;
+use crate::util;
+
+pub trait Executor: fmt::Debug + Send + Sync {
+	/// Whether actions are only printed. Lets callers skip follow-up work
+	/// that depends on a real result, like cleanup guards.
+	fn dry_run(&self) -> bool {
+		false
+	}
+
+	fn create_dir_all(&self, path: &Path) -> anyhow::Result<()>;
+	fn remove_dir_all(&self, path: &Path) -> anyhow::Result<()>;
+	fn remove_file(&self, path: &Path) -> anyhow::Result<()>;
+	fn write(&self, path: &Path, contents: &[u8]) -> anyhow::Result<()>;
+	fn copy(&self, from: &Path, to: &Path) -> anyhow::Result<()>;
+
+	/// Runs a command with inherited stdio, optionally in `dir`.
+	fn run_inherit(
+		&self,
+		program: &str,
+		args: &[&str],
+		dir: Option<&Path>,
+	) -> anyhow::Result<ExitStatus>;
+
+	/// Runs a command and returns its trimmed stdout; fails on a non-zero
+	/// exit. Only for commands that change nothing, as [`DryRun`] runs them
+	/// too.
+	fn run_capture(
+		&self,
+		program: &str,
+		args: &[&str],
+	) -> anyhow::Result<String>;
+
+	fn spawn_shell_background(&self, command: &str) -> anyhow::Result<()>;
+
+	/// Downloads `url` to `path`, verifying `sha256` when given.
+	fn download(
+		&self,
+		url: &str,
+		path: &Path,
+		sha256: Option<&str>,
+	) -> anyhow::Result<()>;
+}
+
+/// Performs every action for real, starting processes through a
+/// [`ProcessRunner`].
+#[derive(Debug)]
+pub struct System {
+	processes: Arc<dyn ProcessRunner>,
+}
+
+impl System {
+	pub fn new(processes: Arc<dyn ProcessRunner>) -> Self {
+		Self { processes }
+	}
+}
+
+impl Default for System {
+	fn default() -> Self {
+		Self::new(Arc::new(Native))
+	}
+}
+
+impl Executor for System {
+	fn create_dir_all(&self, path: &Path) -> anyhow::Result<()> {
+		Ok(std::fs::create_dir_all(path)?)
+	}
+
+	fn remove_dir_all(&self, path: &Path) -> anyhow::Result<()> {
+		Ok(std::fs::remove_dir_all(path)?)
+	}
+
+	fn remove_file(&self, path: &Path) -> anyhow::Result<()> {
+		Ok(std::fs::remove_file(path)?)
+	}
+
+	fn write(&self, path: &Path, contents: &[u8]) -> anyhow::Result<()> {
+		Ok(std::fs::write(path, contents)?)
+	}
+
+	fn copy(&self, from: &Path, to: &Path) -> anyhow::Result<()> {
+		std::fs::copy(from, to)?;
+		Ok(())
+	}
+
+	fn run_inherit(
+		&self,
+		program: &str,
+		args: &[&str],
+		dir: Option<&Path>,
+	) -> anyhow::Result<ExitStatus> {
+		self.processes.run_inherit(program, args, dir)
+	}
+
+	fn run_capture(
+		&self,
+		program: &str,
+		args: &[&str],
+	) -> anyhow::Result<String> {
+		self.processes.run_capture(program, args)
+	}
+
+	fn spawn_shell_background(&self, command: &str) -> anyhow::Result<()> {
+		self.processes.spawn_shell_background(command)
+	}
+
+	fn download(
+		&self,
+		url: &str,
+		path: &Path,
+		sha256: Option<&str>,
+	) -> anyhow::Result<()> {
+		match sha256 {
+			Some(sha256) => {
+				net::download_to_file_with_sha256(url, path, sha256)
+			}
+			None => net::download_to_file(url, path),
+		}
+	}
+}
+
+/// Prints each action instead of performing it. Commands see success
+/// everywhere: processes exit 0 and downloads complete. Captures do run, so
+/// what the command reads about the system is real.
+#[derive(Debug)]
+pub struct DryRun {
+	processes: Arc<dyn ProcessRunner>,
+}
+
+impl DryRun {
+	pub fn new(processes: Arc<dyn ProcessRunner>) -> Self {
+		Self { processes }
+	}
+}
+
+impl Default for DryRun {
+	fn default() -> Self {
+		Self::new(Arc::new(Native))
+	}
+}
+
+fn plan(action: &str) {
+	ui::plan(&format!("dry-run: would {action}"));
+}
+
+impl Executor for DryRun {
+	fn dry_run(&self) -> bool {
+		true
+	}
+
+	fn create_dir_all(&self, path: &Path) -> anyhow::Result<()> {
+		if !path.is_dir() {
+			plan(&format!("create {}", path.display()));
+		}
+		Ok(())
+	}
+
+	fn remove_dir_all(&self, path: &Path) -> anyhow::Result<()> {
+		plan(&format!("delete {}", path.display()));
+		Ok(())
+	}
+
+	fn remove_file(&self, path: &Path) -> anyhow::Result<()> {
+		plan(&format!("delete {}", path.display()));
+		Ok(())
+	}
+
+	fn write(&self, path: &Path, contents: &[u8]) -> anyhow::Result<()> {
+		plan(&format!(
+			"write {} ({})",
+			path.display(),
+			net::format_bytes(contents.len() as u64)
+		));
+		Ok(())
+	}
+
+	fn copy(&self, from: &Path, to: &Path) -> anyhow::Result<()> {
+		plan(&format!("copy {} → {}", from.display(), to.display()));
+		Ok(())
+	}
+
+	fn run_inherit(
+		&self,
+		program: &str,
+		args: &[&str],
+		dir: Option<&Path>,
+	) -> anyhow::Result<ExitStatus> {
+		let line = util::format_command_line(program, args);
+		match dir {
+			Some(dir) => plan(&format!("run `{line}` in {}", dir.display())),
+			None => plan(&format!("run `{line}`")),
+		}
+		Ok(ExitStatus::default())
+	}
+
+	fn run_capture(
+		&self,
+		program: &str,
+		args: &[&str],
+	) -> anyhow::Result<String> {
+		self.processes.run_capture(program, args)
+	}
+
+	fn spawn_shell_background(&self, command: &str) -> anyhow::Result<()> {
+		plan(&format!("run in the background: {command}"));
+		Ok(())
+	}
+
+	fn download(
+		&self,
+		url: &str,
+		path: &Path,
+		sha256: Option<&str>,
+	) -> anyhow::Result<()> {
+		match sha256 {
+			Some(sha256) => plan(&format!(
+				"download {url} → {} (sha256 {sha256})",
+				path.display()
+			)),
+			None => plan(&format!("download {url} → {}", path.display())),
+		}
+		Ok(())
+	}
+}
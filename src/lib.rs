@@ -1,6 +1,18 @@
+pub mod alias;
+pub mod build_info;
+pub mod cleanup;
 pub mod cli;
 pub mod commands;
+pub mod config;
 pub mod context;
+pub mod error;
+pub mod exec;
 pub mod net;
+pub mod notice;
+pub mod paths;
+pub mod plugins;
+pub mod process;
+pub mod runlog;
+pub mod timings;
 pub mod ui;
 pub mod util;
@@ -1,59 +1,256 @@
+use std::ffi::OsString;
+use std::io::IsTerminal as _;
+
 use clap::error::ErrorKind;
+use eagle::alias::Aliases;
 use eagle::context::Context;
+use eagle::timings::Kind;
 
 fn main() {
-	if let Err(err) = run() {
-		eagle::ui::error(&format!("{err}"));
-		std::process::exit(1);
+	let code = match run() {
+		Ok(code) => {
+			eagle::runlog::finish(code, Vec::new());
+			code
+		}
+		Err(err) => {
+			let code = report(&err);
+			eagle::runlog::finish(
+				code,
+				err.chain().map(|c| c.to_string()).collect(),
+			);
+			code
+		}
+	};
+	std::process::exit(code);
+}
+
+/// Prints `err` with its cause chain and hint; returns the exit code.
+fn report(err: &anyhow::Error) -> i32 {
+	let category = eagle::error::category(err);
+	let hint = eagle::error::hint(err);
+	let causes: Vec<String> =
+		err.chain().skip(1).map(|c| c.to_string()).collect();
+
+	if eagle::ui::json_enabled() {
+		let doc = serde_json::json!({
+			"ok": false,
+			"error": format!("{err}"),
+			"category": category.as_str(),
+			"exit_code": category.exit_code(),
+			"causes": causes,
+			"hint": hint,
+			"result": eagle::ui::take_result(),
+		});
+		println!("{doc}");
+	} else {
+		let mut message = format!("{err}");
+		for cause in &causes {
+			message.push_str(&format!("\n  caused by: {cause}"));
+		}
+		eagle::ui::error(message.trim_end());
+		if let Some(hint) = &hint {
+			eagle::ui::hint(hint);
+		}
+	}
+	category.exit_code()
+}
+
+/// Runs on SIGINT/SIGTERM once registered paths are cleaned up: reports the
+/// cancellation like any other error and exits with 130.
+fn interrupted() {
+	let err = eagle::error::cancelled();
+	if !eagle::ui::json_enabled() {
+		// Keep the message off the `^C` the terminal echoed.
+		eprintln!();
+	}
+	let code = report(&err);
+	eagle::runlog::finish(code, vec![err.to_string()]);
+	std::process::exit(code);
+}
+
+/// Prints the `--timings` table to stderr, or writes the Chrome trace file
+/// `target`.
+fn write_timings(target: &str) {
+	let spans = eagle::timings::finish();
+	if target == eagle::cli::TIMINGS_TABLE {
+		eprint!("{}", eagle::timings::table(&spans));
+		return;
+	}
+
+	let trace = eagle::timings::chrome_trace(&spans);
+	match std::fs::write(target, trace.to_string()) {
+		Ok(()) => eagle::ui::muted(&format!("Wrote timings trace to {target}")),
+		Err(err) => eagle::ui::warning(&format!(
+			"Could not write timings trace to {target}: {err}"
+		)),
 	}
 }
 
-fn run() -> anyhow::Result<()> {
+/// Runs the CLI and returns the exit code for a successful run (a plugin may
+/// exit non-zero without that being an eagle error).
+fn run() -> anyhow::Result<i32> {
+	// Answers `COMPLETE=<shell> eagle -- ...` requests from the scripts that
+	// `eagle completions` installs, then exits.
+	clap_complete::CompleteEnv::with_factory(eagle::cli::build_cli).complete();
+
+	let raw_args: Vec<OsString> = std::env::args_os().collect();
+	eagle::runlog::start(&raw_args);
+	eagle::cleanup::install(interrupted)?;
+
 	let mut cmd = eagle::cli::build_cli();
 
-	let matches = match cmd.clone().try_get_matches() {
+	// Alias expansion needs the config before clap parses anything, so
+	// neither loading it nor reading `[alias]` may fail here.
+	let mut ctx = Context::new()?;
+	if let Ok(path) = eagle::runlog::default_path(&ctx.config, &ctx.paths) {
+		eagle::runlog::set_path(path);
+	}
+	let aliases = Aliases::load(&ctx.config, &cmd);
+	if let Some(section) = aliases.help_section() {
+		cmd = cmd.after_help(section);
+	}
+	let args = eagle::alias::expand(raw_args, &aliases);
+
+	let matches = match cmd.clone().try_get_matches_from(&args) {
 		Ok(m) => m,
-		Err(err) if err.kind() == ErrorKind::DisplayHelp => {
+		Err(err)
+			if matches!(
+				err.kind(),
+				ErrorKind::DisplayHelp
+					| ErrorKind::DisplayHelpOnMissingArgumentOrSubcommand
+			) =>
+		{
+			// Parsed again to render help with the tool checks, which every
+			// other invocation skips.
+			let err = eagle::cli::with_tool_marks(cmd)
+				.try_get_matches_from(&args)
+				.err()
+				.unwrap_or(err);
+			if err.kind() != ErrorKind::DisplayHelp {
+				return Err(err.into());
+			}
 			err.print()?;
-			return Ok(());
+			return Ok(0);
 		}
 		Err(err) if err.kind() == ErrorKind::DisplayVersion => {
 			err.print()?;
-			return Ok(());
+			return Ok(0);
 		}
 		Err(err) => return Err(err.into()),
 	};
 
-	let ctx = Context::new()?;
+	eagle::ui::set_json(matches.get_flag("json"));
 
-	if ctx.dev_mode {
-		eagle::ui::debug(&format!("eagle v{}", ctx.version_string()));
-		eagle::ui::debug(&format!("exe: {}", ctx.exe_path.display()));
+	let no_color = std::env::var("NO_COLOR").ok();
+	let clicolor_force = std::env::var("CLICOLOR_FORCE").ok();
+	eagle::ui::set_color_choice(eagle::ui::resolve_color_choice(
+		matches
+			.get_one::<String>("color")
+			.and_then(|v| eagle::ui::ColorChoice::parse(v)),
+		no_color.as_deref(),
+		clicolor_force.as_deref(),
+	));
+
+	let log_env = std::env::var("EAGLE_LOG").ok();
+	eagle::ui::set_level(eagle::ui::resolve_level(
+		matches.get_flag("quiet"),
+		matches.get_count("verbose"),
+		log_env.as_deref(),
+		ctx.dev_mode,
+	));
+
+	eagle::ui::set_interactive(
+		!matches.get_flag("non_interactive") && std::io::stdin().is_terminal(),
+	);
+
+	let timings = matches.get_one::<String>("timings").cloned();
+	if timings.is_some() {
+		eagle::timings::enable();
+	}
+
+	if matches.get_flag("dry_run") {
+		ctx.exec = std::sync::Arc::new(eagle::exec::DryRun::new(
+			ctx.processes.clone(),
+		));
+	}
+
+	eagle::ui::debug(&format!("eagle v{}", ctx.version_string()));
+	eagle::ui::debug(&format!("exe: {}", ctx.exe_path.display()));
+
+	if let Some(err) = ctx.config.load_error() {
+		// One warning, so `-q` hides all of it.
+		eagle::ui::warning(&format!(
+			"Ignoring the config file (fix it with `eagle config edit`): {err}"
+		));
+	}
+	for (name, reason) in &aliases.rejected {
+		eagle::ui::warning(&format!("Ignoring alias '{name}': {reason}"));
 	}
 
 	let (sub_name, sub_matches) = matches.subcommand().ok_or_else(|| {
 		cmd.error(ErrorKind::MissingSubcommand, "missing command")
 	})?;
 
-	if ctx.dev_mode {
-		eagle::ui::debug(&format!("dispatch → {sub_name}"));
+	eagle::ui::debug(&format!("dispatch → {sub_name}"));
+	if sub_name == "logs" {
+		// Reading the log should not add to it.
+		eagle::runlog::discard();
+	} else {
+		eagle::runlog::set_command(sub_name);
 	}
 
 	for spec in eagle::commands::iter_specs() {
 		if spec.name == sub_name {
+			spec.check(sub_matches, &ctx)?;
 			let t0 = std::time::Instant::now();
-			let result = (spec.run)(sub_matches, &ctx);
-			if ctx.dev_mode {
-				eagle::ui::debug(&format!(
-					"finished in {:.1}ms",
-					t0.elapsed().as_secs_f64() * 1000.0
-				));
+			let result = {
+				let _span = eagle::timings::span(Kind::Command, sub_name);
+				(spec.run)(sub_matches, &ctx)
+			};
+			eagle::ui::debug(&format!(
+				"finished in {:.1}ms",
+				t0.elapsed().as_secs_f64() * 1000.0
+			));
+			if let Some(target) = &timings {
+				write_timings(target);
 			}
-			return result;
+			if result.is_ok() {
+				eagle::notice::show(&ctx, sub_name);
+			}
+			if result.is_ok() && eagle::ui::json_enabled() {
+				let doc = serde_json::json!({
+					"ok": true,
+					"command": sub_name,
+					"result": eagle::ui::take_result(),
+				});
+				println!("{doc}");
+			}
+			return result.map(|()| 0);
 		}
 	}
 
-	let suggestion = closest_command_name(&cmd, sub_name);
+	if let Some(path) = eagle::plugins::find(sub_name) {
+		let plugin_args: Vec<OsString> = sub_matches
+			.get_many::<OsString>("")
+			.into_iter()
+			.flatten()
+			.cloned()
+			.collect();
+		eagle::ui::debug(&format!("plugin → {}", path.display()));
+
+		// The plugin reports its own errors; only forward the exit code.
+		let status = {
+			let _span = eagle::timings::span(Kind::Command, sub_name);
+			eagle::plugins::run(&path, &plugin_args, &ctx)?
+		};
+		if let Some(target) = &timings {
+			write_timings(target);
+		}
+		return Ok(status.code().unwrap_or(1));
+	}
+
+	let plugins = eagle::plugins::discover();
+	let suggestion = closest_command_name(&cmd, &plugins, sub_name);
 
 	let msg = match suggestion {
 		Some(s) => {
@@ -67,40 +264,12 @@ fn run() -> anyhow::Result<()> {
 
 fn closest_command_name<'a>(
 	cmd: &'a clap::Command,
+	plugins: &'a [eagle::plugins::Plugin],
 	sub_name: &str,
 ) -> Option<&'a str> {
-	let mut closest = None;
-	let mut closest_distance = usize::MAX;
-
-	for sub in cmd.get_subcommands() {
-		track_candidate(
-			sub_name,
-			sub.get_name(),
-			&mut closest,
-			&mut closest_distance,
-		);
-		for alias in sub.get_all_aliases() {
-			track_candidate(
-				sub_name,
-				alias,
-				&mut closest,
-				&mut closest_distance,
-			);
-		}
-	}
-
-	if closest_distance <= 3 { closest } else { None }
-}
-
-fn track_candidate<'a>(
-	sub_name: &str,
-	candidate: &'a str,
-	closest: &mut Option<&'a str>,
-	closest_distance: &mut usize,
-) {
-	let distance = eagle::util::levenshtein(sub_name, candidate);
-	if distance < *closest_distance {
-		*closest = Some(candidate);
-		*closest_distance = distance;
-	}
+	let plugin_names = plugins.iter().map(|p| p.name.as_str());
+	eagle::util::closest(
+		sub_name,
+		eagle::cli::subcommand_names(cmd).chain(plugin_names),
+	)
 }
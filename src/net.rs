@@ -10,9 +10,15 @@ use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 use std::time::{Duration, Instant};
 
+use crossterm::style::Stylize;
 use serde::de::DeserializeOwned;
 use sha2::{Digest, Sha256};
 
+use crate::cleanup;
+use crate::config::Config;
+use crate::error;
+use crate::runlog;
+use crate::timings::{self, Kind};
 use crate::ui;
 
 const USER_AGENT: &str = concat!("eagle/", env!("CARGO_PKG_VERSION"));
@@ -36,10 +42,78 @@ fn http_agent() -> &'static ureq::Agent {
 fn request_get(
 	url: &str,
 ) -> Result<ureq::http::Response<ureq::Body>, ureq::Error> {
-	http_agent()
-		.get(url)
-		.header("User-Agent", USER_AGENT)
-		.call()
+	request_get_within(url, None)
+}
+
+/// [`request_get`], with `timeout` replacing the agent's overall limit.
+fn request_get_within(
+	url: &str,
+	timeout: Option<Duration>,
+) -> Result<ureq::http::Response<ureq::Body>, ureq::Error> {
+	ui::trace(&format!("http: GET {url}"));
+	let t0 = Instant::now();
+	let mut request = http_agent().get(url).header("User-Agent", USER_AGENT);
+	if timeout.is_some() {
+		request = request.config().timeout_global(timeout).build();
+	}
+	let result = request.call();
+
+	let elapsed_ms = t0.elapsed().as_secs_f64() * 1000.0;
+	match &result {
+		Ok(resp) => {
+			ui::trace(&format!(
+				"http: {} for {url} ({elapsed_ms:.1}ms)",
+				resp.status()
+			));
+			runlog::http(url, Ok(resp.status().as_u16()), t0);
+		}
+		Err(err) => {
+			ui::trace(&format!("http: {err} for {url} ({elapsed_ms:.1}ms)"));
+			runlog::http(url, Err(err.to_string()), t0);
+		}
+	}
+	result
+}
+
+/// Base URLs of the web services eagle talks to. Each can be pointed at a
+/// local stand-in via its `endpoints.*` config key or env var.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Endpoints {
+	pub github_api: String,
+	pub paper_api: String,
+	pub fabric_meta: String,
+}
+
+impl Endpoints {
+	pub fn from_config(config: &Config) -> anyhow::Result<Self> {
+		let base = |key: &str, default: &str| -> anyhow::Result<String> {
+			let value = config.string(key)?;
+			let value = value.as_deref().unwrap_or(default);
+			Ok(value.trim_end_matches('/').to_string())
+		};
+
+		Ok(Self {
+			github_api: base("endpoints.github_api", "https://api.github.com")?,
+			paper_api: base("endpoints.paper_api", "https://fill.papermc.io")?,
+			fabric_meta: base(
+				"endpoints.fabric_meta",
+				"https://meta.fabricmc.net",
+			)?,
+		})
+	}
+}
+
+/// Sends a single GET without retries and returns the HTTP status, error
+/// statuses included. Only transport failures are errors.
+pub fn probe(url: &str) -> anyhow::Result<u16> {
+	let _span = timings::span(Kind::Http, format!("GET {url}"));
+	match request_get(url) {
+		Ok(resp) => Ok(resp.status().as_u16()),
+		Err(ureq::Error::StatusCode(code)) => Ok(code),
+		Err(err) => {
+			Err(anyhow::Error::new(err).context(format!("GET {url} failed")))
+		}
+	}
 }
 
 #[doc(hidden)]
@@ -79,7 +153,8 @@ where
 				if !is_retryable_http_error(&err)
 					|| attempt == MAX_HTTP_ATTEMPTS
 				{
-					return Err(err.into());
+					return Err(anyhow::Error::new(err)
+						.context(format!("{label} failed")));
 				}
 
 				let delay = retry_delay(attempt);
@@ -124,11 +199,65 @@ pub fn temp_download_path(out_path: &Path) -> PathBuf {
 /// Errors if the server response is not `200 OK` or if the body cannot be
 /// deserialized.
 pub fn get_json<T: DeserializeOwned>(url: &str) -> anyhow::Result<T> {
+	let _span = timings::span(Kind::Http, format!("GET {url}"));
 	let resp = call_with_retries(&format!("GET {url}"), || request_get(url))?;
+	read_json(resp, url)
+}
 
+/// Like [`get_json`], but a single attempt that gives up after `timeout`
+/// and never warns, for background checks the user did not ask for.
+pub fn get_json_once<T: DeserializeOwned>(
+	url: &str,
+	timeout: Duration,
+) -> anyhow::Result<T> {
+	let _span = timings::span(Kind::Http, format!("GET {url}"));
+	let resp = request_get_within(url, Some(timeout)).map_err(|err| {
+		anyhow::Error::new(err).context(format!("GET {url} failed"))
+	})?;
+	read_json(resp, url)
+}
+
+/// Like [`get_json`] for a JSON array that is split into pages: follows the
+/// `Link` header's `rel="next"` URL and returns the items of every page.
+pub fn get_json_pages<T: DeserializeOwned>(
+	url: &str,
+) -> anyhow::Result<Vec<T>> {
+	let mut items = Vec::new();
+	let mut next = Some(url.to_string());
+	while let Some(url) = next {
+		let _span = timings::span(Kind::Http, format!("GET {url}"));
+		let resp =
+			call_with_retries(&format!("GET {url}"), || request_get(&url))?;
+		next = resp
+			.headers()
+			.get("link")
+			.and_then(|v| v.to_str().ok())
+			.and_then(next_link)
+			.map(str::to_string);
+		items.extend(read_json::<Vec<T>>(resp, &url)?);
+	}
+	Ok(items)
+}
+
+/// The `rel="next"` URL in a `Link` header, as GitHub sends for lists.
+#[doc(hidden)]
+pub fn next_link(header: &str) -> Option<&str> {
+	header.split(',').find_map(|link| {
+		let (target, params) = link.split_once(';')?;
+		if !params.split(';').any(|p| p.trim() == r#"rel="next""#) {
+			return None;
+		}
+		target.trim().strip_prefix('<')?.strip_suffix('>')
+	})
+}
+
+fn read_json<T: DeserializeOwned>(
+	resp: ureq::http::Response<ureq::Body>,
+	url: &str,
+) -> anyhow::Result<T> {
 	let status = resp.status();
 	if status != 200 {
-		anyhow::bail!("HTTP {status} for {url}");
+		return Err(error::network(format!("HTTP {status} for {url}")));
 	}
 
 	let mut reader = resp.into_body().into_reader();
@@ -141,10 +270,11 @@ pub fn get_json<T: DeserializeOwned>(url: &str) -> anyhow::Result<T> {
 
 /// Performs a blocking HTTP GET and returns response body as UTF-8 text.
 pub fn get_text(url: &str) -> anyhow::Result<String> {
+	let _span = timings::span(Kind::Http, format!("GET {url}"));
 	let resp = call_with_retries(&format!("GET {url}"), || request_get(url))?;
 	let status = resp.status();
 	if status != 200 {
-		anyhow::bail!("HTTP {status} for {url}");
+		return Err(error::network(format!("HTTP {status} for {url}")));
 	}
 
 	let mut reader = resp.into_body().into_reader();
@@ -175,18 +305,22 @@ fn download_to_file_internal(
 	out_path: &Path,
 	expected_sha256: Option<&str>,
 ) -> anyhow::Result<()> {
+	let _span = timings::span(Kind::Http, format!("download {url}"));
 	if let Some(parent) = out_path.parent() {
 		std::fs::create_dir_all(parent)?;
 	}
 
 	let temp_path = temp_download_path(out_path);
 	let _ = std::fs::remove_file(&temp_path);
+	let _pending = cleanup::register(&temp_path);
 
 	let resp = call_with_retries(&format!("GET {url}"), || request_get(url))?;
 
 	let status = resp.status();
 	if status != 200 {
-		anyhow::bail!("Download failed (HTTP {status})");
+		return Err(error::network(format!(
+			"Download failed (HTTP {status}) for {url}"
+		)));
 	}
 
 	let total_bytes = resp
@@ -220,37 +354,44 @@ fn download_to_file_internal(
 		downloaded += n as u64;
 
 		if last_draw.elapsed() >= Duration::from_millis(120) {
-			draw_progress(downloaded, total_bytes)?;
+			ui::progress(&progress_line(downloaded, total_bytes))?;
 			last_draw = Instant::now();
 		}
 	}
 
-	draw_progress(downloaded, total_bytes)?;
-	println!();
+	ui::progress(&progress_line(downloaded, total_bytes))?;
+	ui::progress_done();
 	file.flush()?;
 
 	if let Some(expected) = expected_sha256 {
 		let actual = format!("{:x}", hasher.finalize());
 		if actual != expected {
 			let _ = std::fs::remove_file(&temp_path);
-			anyhow::bail!(
+			return Err(error::integrity(format!(
 				"sha256 mismatch for {}: expected {}, got {}",
 				out_path.display(),
 				expected,
 				actual
-			);
+			)));
 		}
 	}
 
 	let _ = std::fs::remove_file(out_path);
 	std::fs::rename(&temp_path, out_path)?;
 
+	ui::event(
+		"download",
+		serde_json::json!({
+			"url": url,
+			"path": out_path,
+			"bytes": downloaded,
+		}),
+	);
+
 	Ok(())
 }
 
-fn draw_progress(downloaded: u64, total: Option<u64>) -> anyhow::Result<()> {
-	let mut out = std::io::stdout();
-
+fn progress_line(downloaded: u64, total: Option<u64>) -> String {
 	match total {
 		Some(total) if total > 0 => {
 			let pct = (downloaded as f64 / total as f64).min(1.0);
@@ -259,22 +400,27 @@ fn draw_progress(downloaded: u64, total: Option<u64>) -> anyhow::Result<()> {
 			let filled = filled.min(width);
 			let empty = width.saturating_sub(filled);
 
-			let bar = format!("[{}{}]", "#".repeat(filled), ".".repeat(empty));
+			let bar = if ui::stdout_colors() {
+				format!(
+					"[{}{}]",
+					"#".repeat(filled).green(),
+					".".repeat(empty).dark_grey()
+				)
+			} else {
+				format!("[{}{}]", "#".repeat(filled), ".".repeat(empty))
+			};
 
 			let pct_s = format!("{:>3}%", (pct * 100.0).round() as u64);
 			let cur = format_bytes(downloaded);
 			let tot = format_bytes(total);
 
-			print!("\r{bar} {pct_s} {cur}/{tot}");
+			format!("{bar} {pct_s} {cur}/{tot}")
 		}
 		_ => {
 			let cur = format_bytes(downloaded);
-			print!("\rDownloading... {cur}");
+			format!("Downloading... {cur}")
 		}
 	}
-
-	out.flush()?;
-	Ok(())
 }
 
 #[doc(hidden)]
@@ -0,0 +1,129 @@
+//! Passive "new version available" notice.
+//!
+//! After a command, `main` calls [`show`]. At most once per [`INTERVAL`] it
+//! asks GitHub for the latest release, the same endpoint `eagle update`
+//! uses, and caches the answer in [`CACHE_FILE`] in the data dir; in between
+//! it only reads the cache. The check is a single request that gives up
+//! after [`CHECK_TIMEOUT`] without a word, so being offline costs nothing.
+//!
+//! `update.notify = false` (or `EAGLE_UPDATE_NOTIFY=false`) turns it off;
+//! `true` also shows it in dev builds and piped output.
+
+use std::io::IsTerminal as _;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::commands::update::{self, Channel};
+use crate::context::Context;
+use crate::net::Endpoints;
+use crate::paths::Paths;
+use crate::ui;
+
+pub const CACHE_FILE: &str = "update-check.json";
+pub const INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+pub const CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Commands that already report the latest version themselves.
+const SKIP_COMMANDS: &[&str] = &["update", "version"];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cache {
+	/// Unix seconds of the last check.
+	pub checked_at: u64,
+	/// `None` when that check failed; it is retried after [`INTERVAL`].
+	pub latest_version: Option<String>,
+}
+
+pub fn cache_path(paths: &Paths) -> anyhow::Result<PathBuf> {
+	Ok(paths.data_dir()?.join(CACHE_FILE))
+}
+
+pub fn read_cache(path: &Path) -> Option<Cache> {
+	let text = std::fs::read_to_string(path).ok()?;
+	serde_json::from_str(&text).ok()
+}
+
+pub fn write_cache(path: &Path, cache: &Cache) -> anyhow::Result<()> {
+	if let Some(parent) = path.parent() {
+		std::fs::create_dir_all(parent)?;
+	}
+	std::fs::write(path, serde_json::to_vec(cache)?)?;
+	Ok(())
+}
+
+pub fn now() -> u64 {
+	SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map(|d| d.as_secs())
+		.unwrap_or(0)
+}
+
+/// The latest release version: from the cache at `path` when it is younger
+/// than [`INTERVAL`] at `now`, otherwise fetched and cached.
+pub fn latest_version(
+	endpoints: &Endpoints,
+	channel: Channel,
+	path: &Path,
+	now: u64,
+) -> Option<String> {
+	if let Some(cache) = read_cache(path)
+		&& now.saturating_sub(cache.checked_at) < INTERVAL.as_secs()
+	{
+		return cache.latest_version;
+	}
+
+	let latest =
+		update::latest_version_within(endpoints, channel, CHECK_TIMEOUT)
+			.inspect_err(|err| {
+				ui::debug(&format!("update check failed: {err}"))
+			})
+			.ok();
+	let cache = Cache {
+		checked_at: now,
+		latest_version: latest.clone(),
+	};
+	if let Err(err) = write_cache(path, &cache) {
+		ui::debug(&format!("could not cache update check: {err}"));
+	}
+	latest
+}
+
+/// The notice line when `latest` is newer than `current`.
+pub fn message(current: &str, latest: &str) -> Option<String> {
+	update::is_newer(latest, current).then(|| {
+		format!(
+			"eagle v{latest} is available (you have v{current}). Run `eagle update` to install it."
+		)
+	})
+}
+
+/// Prints the notice after `command` when a newer release exists. Stays
+/// quiet for JSON output and when turned off, and unless `update.notify` is
+/// explicitly on, for dev builds and piped output.
+pub fn show(ctx: &Context, command: &str) {
+	let notify = ctx.config.bool("update.notify").ok().flatten();
+	if notify == Some(false)
+		|| SKIP_COMMANDS.contains(&command)
+		|| ui::json_enabled()
+	{
+		return;
+	}
+	if notify.is_none() && (ctx.dev_mode || !std::io::stdout().is_terminal()) {
+		return;
+	}
+
+	let (Ok(endpoints), Ok(channel), Ok(path)) = (
+		Endpoints::from_config(&ctx.config),
+		Channel::from_config(&ctx.config),
+		cache_path(&ctx.paths),
+	) else {
+		return;
+	};
+	if let Some(line) = latest_version(&endpoints, channel, &path, now())
+		.and_then(|latest| message(ctx.version, &latest))
+	{
+		ui::info(&line);
+	}
+}
@@ -0,0 +1,112 @@
+//! Where eagle keeps its state.
+//!
+//! Without `EAGLE_HOME` everything lives in the usual platform folders. With
+//! it, every default moves under that one root:
+//!
+//! | What | Default | Under `EAGLE_HOME` |
+//! |---|---|---|
+//! | config | `<config dir>/eagle/config.toml` | `config.toml` |
+//! | run log and other data | `<data dir>/eagle` | `.` |
+//! | EagleCord clone | `<data dir>/EagleCord` | `eaglecord` |
+//! | Minecraft servers | `~/Documents/mc-servers` | `mc-servers` |
+//! | `create` projects | `~/Development/.YY` | `projects/.YY` |
+//!
+//! The per-path settings (`EAGLE_CONFIG`, `minecraft.servers_root`, …) still
+//! win over both.
+
+use std::path::PathBuf;
+
+use crate::config::expand_tilde;
+
+pub const HOME_ENV: &str = "EAGLE_HOME";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Paths {
+	home: Option<PathBuf>,
+}
+
+impl Paths {
+	/// Uses `home` as the root, or the platform folders when `None`.
+	pub fn new(home: Option<PathBuf>) -> Self {
+		Self { home }
+	}
+
+	/// Reads `EAGLE_HOME`; unset or empty means the platform folders.
+	pub fn from_env() -> Self {
+		let home = std::env::var(HOME_ENV)
+			.ok()
+			.map(|v| v.trim().to_string())
+			.filter(|v| !v.is_empty())
+			.map(|v| expand_tilde(&v));
+		Self::new(home)
+	}
+
+	/// The `EAGLE_HOME` root, if one is set.
+	pub fn home(&self) -> Option<&PathBuf> {
+		self.home.as_ref()
+	}
+
+	pub fn config_file(&self) -> anyhow::Result<PathBuf> {
+		if let Some(home) = &self.home {
+			return Ok(home.join("config.toml"));
+		}
+
+		let config_dir = directories::BaseDirs::new()
+			.map(|b| b.config_dir().to_path_buf())
+			.ok_or_else(|| {
+				anyhow::anyhow!("Could not resolve config directory")
+			})?;
+		Ok(config_dir.join("eagle").join("config.toml"))
+	}
+
+	/// Folder for eagle's own data files, like the run log.
+	pub fn data_dir(&self) -> anyhow::Result<PathBuf> {
+		if let Some(home) = &self.home {
+			return Ok(home.clone());
+		}
+		Ok(platform_data_dir()?.join("eagle"))
+	}
+
+	/// Folder that holds the EagleCord clone.
+	pub fn eaglecord_dir(&self) -> anyhow::Result<PathBuf> {
+		if let Some(home) = &self.home {
+			return Ok(home.join("eaglecord"));
+		}
+		// On macOS this is ~/Library/Application Support/EagleCord.
+		Ok(platform_data_dir()?.join("EagleCord"))
+	}
+
+	pub fn servers_root(&self) -> anyhow::Result<PathBuf> {
+		if let Some(home) = &self.home {
+			return Ok(home.join("mc-servers"));
+		}
+
+		let documents = directories::UserDirs::new()
+			.and_then(|u| u.document_dir().map(|p| p.to_path_buf()))
+			.ok_or_else(|| {
+				anyhow::anyhow!("Could not resolve Documents dir")
+			})?;
+		Ok(documents.join("mc-servers"))
+	}
+
+	/// Base folder for `eagle create` projects in the two-digit `year`.
+	pub fn projects_root(&self, year: &str) -> anyhow::Result<PathBuf> {
+		let year_dir = format!(".{year}");
+		if let Some(home) = &self.home {
+			return Ok(home.join("projects").join(year_dir));
+		}
+
+		let home = directories::UserDirs::new()
+			.map(|u| u.home_dir().to_path_buf())
+			.ok_or_else(|| {
+				anyhow::anyhow!("Could not resolve user home directory")
+			})?;
+		Ok(home.join("Development").join(year_dir))
+	}
+}
+
+fn platform_data_dir() -> anyhow::Result<PathBuf> {
+	directories::BaseDirs::new()
+		.map(|b| b.data_dir().to_path_buf())
+		.ok_or_else(|| anyhow::anyhow!("Could not resolve data directory"))
+}
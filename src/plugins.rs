@@ -0,0 +1,133 @@
+//! External subcommands: an executable named `eagle-<name>` on `PATH` runs as
+//! `eagle <name>`, cargo/git style. Built-in commands always win.
+
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus, Stdio};
+
+use crate::context::Context;
+use crate::runlog;
+use crate::ui;
+use crate::util;
+
+const PREFIX: &str = "eagle-";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plugin {
+	pub name: String,
+	pub path: PathBuf,
+}
+
+/// Returns the plugins found on `PATH`, sorted by name. When several
+/// directories provide the same name, the first one wins like a shell lookup.
+pub fn discover() -> Vec<Plugin> {
+	let path = std::env::var_os("PATH").unwrap_or_default();
+	discover_in(std::env::split_paths(&path))
+}
+
+#[doc(hidden)]
+pub fn discover_in(dirs: impl IntoIterator<Item = PathBuf>) -> Vec<Plugin> {
+	let mut out: Vec<Plugin> = Vec::new();
+
+	for dir in dirs {
+		let Ok(read_dir) = std::fs::read_dir(&dir) else {
+			continue;
+		};
+
+		for entry in read_dir.flatten() {
+			let path = entry.path();
+			let Some(name) = plugin_name(&path) else {
+				continue;
+			};
+			if !is_executable(&path) || out.iter().any(|p| p.name == name) {
+				continue;
+			}
+			out.push(Plugin { name, path });
+		}
+	}
+
+	out.sort_by(|a, b| a.name.cmp(&b.name));
+	out
+}
+
+fn plugin_name(path: &Path) -> Option<String> {
+	let stem = if cfg!(windows) {
+		path.file_stem()?
+	} else {
+		path.file_name()?
+	};
+	let name = stem.to_str()?.strip_prefix(PREFIX)?;
+	(!name.is_empty()).then(|| name.to_string())
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+	use std::os::unix::fs::PermissionsExt;
+
+	path.metadata()
+		.is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+	path.is_file()
+		&& path
+			.extension()
+			.is_some_and(|ext| ext.eq_ignore_ascii_case("exe"))
+}
+
+/// Renders the plugins as an extra help section, if there are any.
+pub fn help_section(plugins: &[Plugin]) -> Option<String> {
+	let width = plugins.iter().map(|p| p.name.len()).max()?;
+	let lines: Vec<String> = plugins
+		.iter()
+		.map(|p| format!("  {:<width$}  {}", p.name, p.path.display()))
+		.collect();
+	Some(format!("Plugins:\n{}", lines.join("\n")))
+}
+
+/// Looks up `eagle-<name>` on `PATH`.
+pub fn find(name: &str) -> Option<PathBuf> {
+	which::which(format!("{PREFIX}{name}")).ok()
+}
+
+/// Runs a plugin with inherited stdio, passing eagle's context through
+/// `EAGLE_EXE`, `EAGLE_VERSION`, `EAGLE_DEV_MODE`, `EAGLE_CONFIG`,
+/// `EAGLE_DRY_RUN` and `EAGLE_NON_INTERACTIVE`.
+pub fn run(
+	path: &Path,
+	args: &[OsString],
+	ctx: &Context,
+) -> anyhow::Result<ExitStatus> {
+	let mut cmd = Command::new(path);
+	cmd.args(args)
+		.env("EAGLE_EXE", &ctx.exe_path)
+		.env("EAGLE_VERSION", ctx.version)
+		.env("EAGLE_DEV_MODE", if ctx.dev_mode { "1" } else { "0" })
+		.env("EAGLE_CONFIG", ctx.config.path())
+		.env("EAGLE_DRY_RUN", if ctx.exec.dry_run() { "1" } else { "0" })
+		.env(
+			"EAGLE_NON_INTERACTIVE",
+			if ui::interactive() { "0" } else { "1" },
+		)
+		.stdin(Stdio::inherit())
+		.stdout(util::child_stdout())
+		.stderr(Stdio::inherit());
+
+	let program = path.to_string_lossy();
+	let args_s: Vec<String> = args
+		.iter()
+		.map(|a| a.to_string_lossy().into_owned())
+		.collect();
+	let args_s: Vec<&str> = args_s.iter().map(String::as_str).collect();
+	ui::trace(&format!(
+		"run: {}",
+		util::format_command_line(&program, &args_s)
+	));
+
+	let started = std::time::Instant::now();
+	let status = cmd.status()?;
+	ui::trace(&format!("exit: {program} → {status}"));
+	runlog::process(&program, &args_s, None, status.code(), started);
+	Ok(status)
+}
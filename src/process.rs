@@ -0,0 +1,240 @@
+//! Subprocesses behind a trait.
+//!
+//! Every `git`, `bun`, `brew` and `java` call, and every `PATH` lookup for
+//! them, goes through the [`ProcessRunner`] on
+//! [`Context`](crate::context::Context). [`Native`] spawns real processes;
+//! [`Scripted`] answers from canned replies and records each call, so the
+//! command flows can be tested without those tools installed.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::ExitStatus;
+use std::sync::Mutex;
+
+use crate::util;
+
+pub trait ProcessRunner: fmt::Debug + Send + Sync {
+	/// Resolves `program` on `PATH`.
+	fn which(&self, program: &str) -> Option<PathBuf>;
+
+	/// Runs a command with inherited stdio, optionally in `dir`.
+	fn run_inherit(
+		&self,
+		program: &str,
+		args: &[&str],
+		dir: Option<&Path>,
+	) -> anyhow::Result<ExitStatus>;
+
+	/// Runs a command and returns its trimmed stdout; fails on a non-zero
+	/// exit.
+	fn run_capture(
+		&self,
+		program: &str,
+		args: &[&str],
+	) -> anyhow::Result<String>;
+
+	/// Runs a command and returns stdout followed by stderr, whatever the
+	/// exit status.
+	fn run_capture_combined(
+		&self,
+		program: &str,
+		args: &[&str],
+	) -> anyhow::Result<String>;
+
+	fn spawn_shell_background(&self, command: &str) -> anyhow::Result<()>;
+}
+
+/// Spawns real processes through [`util`].
+#[derive(Debug, Default)]
+pub struct Native;
+
+impl ProcessRunner for Native {
+	fn which(&self, program: &str) -> Option<PathBuf> {
+		which::which(program).ok()
+	}
+
+	fn run_inherit(
+		&self,
+		program: &str,
+		args: &[&str],
+		dir: Option<&Path>,
+	) -> anyhow::Result<ExitStatus> {
+		match dir {
+			Some(dir) => util::run_inherit_with_dir(program, args, dir),
+			None => util::run_inherit(program, args),
+		}
+	}
+
+	fn run_capture(
+		&self,
+		program: &str,
+		args: &[&str],
+	) -> anyhow::Result<String> {
+		util::run_capture(program, args)
+	}
+
+	fn run_capture_combined(
+		&self,
+		program: &str,
+		args: &[&str],
+	) -> anyhow::Result<String> {
+		util::run_capture_combined(program, args)
+	}
+
+	fn spawn_shell_background(&self, command: &str) -> anyhow::Result<()> {
+		util::spawn_shell_background(command)
+	}
+}
+
+/// One process started through a [`Scripted`] runner.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Call {
+	pub program: String,
+	pub args: Vec<String>,
+	pub dir: Option<PathBuf>,
+}
+
+impl Call {
+	/// The program and its args joined by spaces, as matched by
+	/// [`Scripted::reply`].
+	pub fn line(&self) -> String {
+		std::iter::once(self.program.as_str())
+			.chain(self.args.iter().map(String::as_str))
+			.collect::<Vec<_>>()
+			.join(" ")
+	}
+}
+
+#[derive(Debug, Clone)]
+struct Reply {
+	prefix: String,
+	code: i32,
+	stdout: String,
+}
+
+/// Answers calls from a script instead of spawning anything.
+///
+/// Every program is found on `PATH` unless marked [`Scripted::missing`].
+/// A call takes the first [`Scripted::reply`] whose prefix its
+/// [`Call::line`] starts with; calls without one exit 0 with no output.
+#[derive(Debug, Default)]
+pub struct Scripted {
+	missing: Vec<String>,
+	replies: Vec<Reply>,
+	calls: Mutex<Vec<Call>>,
+}
+
+impl Scripted {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Makes [`ProcessRunner::which`] fail for `program`.
+	pub fn missing(mut self, program: &str) -> Self {
+		self.missing.push(program.to_string());
+		self
+	}
+
+	/// Answers calls whose line starts with `prefix` with an exit code and
+	/// stdout.
+	pub fn reply(mut self, prefix: &str, code: i32, stdout: &str) -> Self {
+		self.replies.push(Reply {
+			prefix: prefix.to_string(),
+			code,
+			stdout: stdout.to_string(),
+		});
+		self
+	}
+
+	/// Returns the calls made so far, in order.
+	pub fn calls(&self) -> Vec<Call> {
+		self.calls.lock().map(|c| c.clone()).unwrap_or_default()
+	}
+
+	/// Returns [`Call::line`] for each call made so far.
+	pub fn lines(&self) -> Vec<String> {
+		self.calls().iter().map(Call::line).collect()
+	}
+
+	fn answer(
+		&self,
+		program: &str,
+		args: &[&str],
+		dir: Option<&Path>,
+	) -> (i32, String) {
+		let call = Call {
+			program: program.to_string(),
+			args: args.iter().map(|a| a.to_string()).collect(),
+			dir: dir.map(Path::to_path_buf),
+		};
+		let line = call.line();
+		if let Ok(mut calls) = self.calls.lock() {
+			calls.push(call);
+		}
+
+		self.replies
+			.iter()
+			.find(|r| line.starts_with(&r.prefix))
+			.map_or((0, String::new()), |r| (r.code, r.stdout.clone()))
+	}
+}
+
+impl ProcessRunner for Scripted {
+	fn which(&self, program: &str) -> Option<PathBuf> {
+		if self.missing.iter().any(|m| m == program) {
+			return None;
+		}
+		Some(PathBuf::from(program))
+	}
+
+	fn run_inherit(
+		&self,
+		program: &str,
+		args: &[&str],
+		dir: Option<&Path>,
+	) -> anyhow::Result<ExitStatus> {
+		let (code, _) = self.answer(program, args, dir);
+		Ok(exit_status(code))
+	}
+
+	fn run_capture(
+		&self,
+		program: &str,
+		args: &[&str],
+	) -> anyhow::Result<String> {
+		match self.answer(program, args, None) {
+			(0, stdout) => Ok(stdout.trim().to_string()),
+			(code, _) => Err(util::capture_failed(
+				program,
+				args,
+				&format!("exit code {code}"),
+			)),
+		}
+	}
+
+	fn run_capture_combined(
+		&self,
+		program: &str,
+		args: &[&str],
+	) -> anyhow::Result<String> {
+		let (_, stdout) = self.answer(program, args, None);
+		Ok(stdout.trim().to_string())
+	}
+
+	fn spawn_shell_background(&self, command: &str) -> anyhow::Result<()> {
+		self.answer("sh", &["-c", command], None);
+		Ok(())
+	}
+}
+
+#[cfg(unix)]
+fn exit_status(code: i32) -> ExitStatus {
+	use std::os::unix::process::ExitStatusExt as _;
+	ExitStatus::from_raw(code << 8)
+}
+
+#[cfg(windows)]
+fn exit_status(code: i32) -> ExitStatus {
+	use std::os::windows::process::ExitStatusExt as _;
+	ExitStatus::from_raw(code as u32)
+}
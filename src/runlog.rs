@@ -0,0 +1,251 @@
+//! Persistent run log.
+//!
+//! Every invocation appends one JSON line to `runs.jsonl` in the data dir:
+//! args, duration, exit status, the error chain and the HTTP requests and
+//! subprocesses it made. `net` and `util` report into the current record
+//! while a command runs; `main` writes it out on exit. The file rotates once
+//! it grows past [`MAX_BYTES`], keeping [`KEEP_ROTATED`] older files.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::paths::Paths;
+
+pub const MAX_BYTES: u64 = 1024 * 1024;
+pub const KEEP_ROTATED: usize = 3;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Record {
+	/// Start time, RFC 3339 in UTC.
+	pub time: String,
+	pub args: Vec<String>,
+	#[serde(default)]
+	pub command: Option<String>,
+	pub duration_ms: u64,
+	pub exit_code: i32,
+	#[serde(default)]
+	pub errors: Vec<String>,
+	#[serde(default)]
+	pub http: Vec<HttpRecord>,
+	#[serde(default)]
+	pub processes: Vec<ProcessRecord>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HttpRecord {
+	pub method: String,
+	pub url: String,
+	#[serde(default)]
+	pub status: Option<u16>,
+	#[serde(default)]
+	pub error: Option<String>,
+	pub duration_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessRecord {
+	pub program: String,
+	pub args: Vec<String>,
+	#[serde(default)]
+	pub dir: Option<PathBuf>,
+	#[serde(default)]
+	pub exit_code: Option<i32>,
+	pub duration_ms: u64,
+}
+
+struct Current {
+	started: Instant,
+	path: Option<PathBuf>,
+	record: Record,
+}
+
+static CURRENT: Mutex<Option<Current>> = Mutex::new(None);
+
+fn with_current(f: impl FnOnce(&mut Current)) {
+	if let Ok(mut slot) = CURRENT.lock()
+		&& let Some(current) = slot.as_mut()
+	{
+		f(current);
+	}
+}
+
+/// Returns the log file: `log.path` from env/config, or `runs.jsonl` in the
+/// eagle data dir.
+pub fn default_path(config: &Config, paths: &Paths) -> anyhow::Result<PathBuf> {
+	if let Some(path) = config.path_value("log.path")? {
+		return Ok(path);
+	}
+
+	Ok(paths.data_dir()?.join("runs.jsonl"))
+}
+
+/// Starts the record for this invocation. Nothing is written until
+/// [`set_path`] and [`finish`] are called.
+pub fn start(args: &[std::ffi::OsString]) {
+	let now = time::OffsetDateTime::now_utc();
+	let time = now
+		.replace_nanosecond(0)
+		.unwrap_or(now)
+		.format(&time::format_description::well_known::Rfc3339)
+		.unwrap_or_default();
+	let record = Record {
+		time,
+		args: args
+			.iter()
+			.skip(1)
+			.map(|a| a.to_string_lossy().into_owned())
+			.collect(),
+		..Record::default()
+	};
+
+	if let Ok(mut slot) = CURRENT.lock() {
+		*slot = Some(Current {
+			started: Instant::now(),
+			path: None,
+			record,
+		});
+	}
+}
+
+pub fn set_path(path: PathBuf) {
+	with_current(|c| c.path = Some(path));
+}
+
+pub fn set_command(name: &str) {
+	with_current(|c| c.record.command = Some(name.to_string()));
+}
+
+/// Drops the current record so the run is not logged.
+pub fn discard() {
+	if let Ok(mut slot) = CURRENT.lock() {
+		*slot = None;
+	}
+}
+
+pub fn http(url: &str, result: Result<u16, String>, started: Instant) {
+	with_current(|c| {
+		let (status, error) = match result {
+			Ok(status) => (Some(status), None),
+			Err(err) => (None, Some(err)),
+		};
+		c.record.http.push(HttpRecord {
+			method: "GET".to_string(),
+			url: url.to_string(),
+			status,
+			error,
+			duration_ms: elapsed_ms(started),
+		});
+	});
+}
+
+pub fn process(
+	program: &str,
+	args: &[&str],
+	dir: Option<&Path>,
+	exit_code: Option<i32>,
+	started: Instant,
+) {
+	with_current(|c| {
+		c.record.processes.push(ProcessRecord {
+			program: program.to_string(),
+			args: args.iter().map(|a| a.to_string()).collect(),
+			dir: dir.map(Path::to_path_buf),
+			exit_code,
+			duration_ms: elapsed_ms(started),
+		});
+	});
+}
+
+/// Completes the record and appends it to the log. Logging must never fail
+/// a run, so write errors are ignored.
+pub fn finish(exit_code: i32, errors: Vec<String>) {
+	let Some(current) = CURRENT.lock().ok().and_then(|mut s| s.take()) else {
+		return;
+	};
+	let Some(path) = current.path else {
+		return;
+	};
+
+	let mut record = current.record;
+	record.duration_ms = elapsed_ms(current.started);
+	record.exit_code = exit_code;
+	record.errors = errors;
+
+	let _ = append(&path, &record);
+}
+
+fn elapsed_ms(started: Instant) -> u64 {
+	u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX)
+}
+
+#[doc(hidden)]
+pub fn append(path: &Path, record: &Record) -> anyhow::Result<()> {
+	if let Some(parent) = path.parent() {
+		std::fs::create_dir_all(parent)?;
+	}
+	if std::fs::metadata(path).is_ok_and(|m| m.len() >= MAX_BYTES) {
+		rotate(path)?;
+	}
+
+	let mut line = serde_json::to_string(record)?;
+	line.push('\n');
+	std::fs::OpenOptions::new()
+		.create(true)
+		.append(true)
+		.open(path)?
+		.write_all(line.as_bytes())?;
+	Ok(())
+}
+
+/// `runs.jsonl` → `runs.1.jsonl` → `runs.2.jsonl` …, dropping the oldest.
+fn rotate(path: &Path) -> anyhow::Result<()> {
+	for n in (1..KEEP_ROTATED).rev() {
+		let from = rotated_path(path, n);
+		if from.exists() {
+			std::fs::rename(&from, rotated_path(path, n + 1))?;
+		}
+	}
+	std::fs::rename(path, rotated_path(path, 1))?;
+	Ok(())
+}
+
+#[doc(hidden)]
+pub fn rotated_path(path: &Path, n: usize) -> PathBuf {
+	let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("runs");
+	let ext = path.extension().and_then(|s| s.to_str()).unwrap_or("jsonl");
+	path.with_file_name(format!("{stem}.{n}.{ext}"))
+}
+
+/// Reads every record, oldest first, across the rotated files. Lines that do
+/// not parse are skipped.
+pub fn read_all(path: &Path) -> anyhow::Result<Vec<Record>> {
+	let mut files: Vec<PathBuf> = (1..=KEEP_ROTATED)
+		.rev()
+		.map(|n| rotated_path(path, n))
+		.collect();
+	files.push(path.to_path_buf());
+
+	let mut out = Vec::new();
+	for file in files {
+		let text = match std::fs::read_to_string(&file) {
+			Ok(text) => text,
+			Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+			Err(err) => return Err(err.into()),
+		};
+		out.extend(parse_lines(&text));
+	}
+	Ok(out)
+}
+
+#[doc(hidden)]
+pub fn parse_lines(text: &str) -> Vec<Record> {
+	text.lines()
+		.filter(|line| !line.trim().is_empty())
+		.filter_map(|line| serde_json::from_str(line).ok())
+		.collect()
+}
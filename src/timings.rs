@@ -0,0 +1,172 @@
+//! Span timings for `--timings`.
+//!
+//! `net` opens a span per HTTP request, `util` one per subprocess, and
+//! commands wrap their named steps in [`phase`]. Nothing is kept until
+//! [`enable`] is called; `main` then prints the spans as a [`table`] or
+//! writes them as a [`chrome_trace`] for `chrome://tracing` / Perfetto.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+	/// The whole command run.
+	Command,
+	/// A named step inside a command.
+	Phase,
+	Http,
+	Process,
+}
+
+impl Kind {
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::Command => "command",
+			Self::Phase => "phase",
+			Self::Http => "http",
+			Self::Process => "process",
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+	pub kind: Kind,
+	pub name: String,
+	/// How many spans were open around this one.
+	pub depth: usize,
+	/// Offset from [`enable`].
+	pub start: Duration,
+	pub duration: Duration,
+}
+
+struct Recorder {
+	origin: Instant,
+	depth: usize,
+	spans: Vec<Span>,
+}
+
+static RECORDER: Mutex<Option<Recorder>> = Mutex::new(None);
+
+fn with_recorder<T>(f: impl FnOnce(&mut Recorder) -> T) -> Option<T> {
+	RECORDER.lock().ok()?.as_mut().map(f)
+}
+
+/// Starts collecting spans; until then [`span`] and [`phase`] cost nothing.
+pub fn enable() {
+	if let Ok(mut slot) = RECORDER.lock() {
+		*slot = Some(Recorder {
+			origin: Instant::now(),
+			depth: 0,
+			spans: Vec::new(),
+		});
+	}
+}
+
+/// An open span; recorded when dropped.
+#[must_use = "the span ends when this guard is dropped"]
+pub struct Guard {
+	open: Option<(Kind, String, usize, Duration)>,
+}
+
+impl Drop for Guard {
+	fn drop(&mut self) {
+		let Some((kind, name, depth, start)) = self.open.take() else {
+			return;
+		};
+		with_recorder(|r| {
+			r.depth = depth;
+			let duration = r.origin.elapsed().saturating_sub(start);
+			r.spans.push(Span {
+				kind,
+				name,
+				depth,
+				start,
+				duration,
+			});
+		});
+	}
+}
+
+/// Opens a span that ends when the returned guard is dropped.
+pub fn span(kind: Kind, name: impl Into<String>) -> Guard {
+	let open = with_recorder(|r| {
+		let depth = r.depth;
+		r.depth += 1;
+		(kind, name.into(), depth, r.origin.elapsed())
+	});
+	Guard { open }
+}
+
+/// Runs `f` as the named phase of the current command.
+pub fn phase<T>(name: &str, f: impl FnOnce() -> T) -> T {
+	let _span = span(Kind::Phase, name);
+	f()
+}
+
+/// Stops collecting and returns the spans in start order.
+pub fn finish() -> Vec<Span> {
+	let mut spans = RECORDER
+		.lock()
+		.ok()
+		.and_then(|mut slot| slot.take())
+		.map(|r| r.spans)
+		.unwrap_or_default();
+	spans.sort_by_key(|s| (s.start, s.depth));
+	spans
+}
+
+/// Renders `spans` as a breakdown table, nested spans indented below the
+/// one they ran in. Shares are of the longest top-level span.
+pub fn table(spans: &[Span]) -> String {
+	let total = spans
+		.iter()
+		.filter(|s| s.depth == 0)
+		.map(|s| s.duration)
+		.max()
+		.unwrap_or_default();
+
+	let mut out = format!("{:>10}  {:>4}  {:<7}  Span\n", "Time", "%", "Kind");
+	for span in spans {
+		let share = if total.is_zero() {
+			0.0
+		} else {
+			span.duration.as_secs_f64() / total.as_secs_f64() * 100.0
+		};
+		out.push_str(&format!(
+			"{:>10}  {share:>3.0}%  {:<7}  {}{}\n",
+			format_duration(span.duration),
+			span.kind.as_str(),
+			"  ".repeat(span.depth),
+			span.name,
+		));
+	}
+	out
+}
+
+/// Renders `spans` in the Chrome trace event format.
+pub fn chrome_trace(spans: &[Span]) -> serde_json::Value {
+	let events: Vec<serde_json::Value> = spans
+		.iter()
+		.map(|s| {
+			serde_json::json!({
+				"name": s.name,
+				"cat": s.kind.as_str(),
+				"ph": "X",
+				"ts": s.start.as_micros() as u64,
+				"dur": s.duration.as_micros() as u64,
+				"pid": std::process::id(),
+				"tid": 1,
+			})
+		})
+		.collect();
+	serde_json::json!({ "traceEvents": events, "displayTimeUnit": "ms" })
+}
+
+fn format_duration(d: Duration) -> String {
+	if d >= Duration::from_secs(1) {
+		format!("{:.2}s", d.as_secs_f64())
+	} else {
+		format!("{:.1}ms", d.as_secs_f64() * 1000.0)
+	}
+}
@@ -1,17 +1,250 @@
-use std::io::IsTerminal;
+use std::io::{IsTerminal, Write};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
 
 use crossterm::style::Stylize;
 
-fn stdout_colors() -> bool {
-	std::io::stdout().is_terminal()
+static JSON: AtomicBool = AtomicBool::new(false);
+static INTERACTIVE: AtomicBool = AtomicBool::new(true);
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Info as u8);
+static COLOR: AtomicU8 = AtomicU8::new(ColorChoice::Auto as u8);
+static RESULT: Mutex<Option<serde_json::Value>> = Mutex::new(None);
+
+/// Output verbosity. Each `ui` function prints only when the current level
+/// is at least its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+	Error = 0,
+	Warn = 1,
+	Info = 2,
+	Debug = 3,
+	Trace = 4,
+}
+
+impl Level {
+	/// Parses an `EAGLE_LOG` value.
+	pub fn parse(value: &str) -> Option<Self> {
+		match value.trim().to_ascii_lowercase().as_str() {
+			"error" | "quiet" => Some(Self::Error),
+			"warn" | "warning" => Some(Self::Warn),
+			"info" => Some(Self::Info),
+			"debug" => Some(Self::Debug),
+			"trace" => Some(Self::Trace),
+			_ => None,
+		}
+	}
+
+	fn from_u8(value: u8) -> Self {
+		match value {
+			0 => Self::Error,
+			1 => Self::Warn,
+			2 => Self::Info,
+			3 => Self::Debug,
+			_ => Self::Trace,
+		}
+	}
+}
+
+/// Resolves the level from `-q`, the `-v` count, `EAGLE_LOG` and the build
+/// type, in that order of precedence.
+#[doc(hidden)]
+pub fn resolve_level(
+	quiet: bool,
+	verbose: u8,
+	env: Option<&str>,
+	dev_mode: bool,
+) -> Level {
+	if quiet {
+		return Level::Error;
+	}
+
+	match verbose {
+		0 => {}
+		1 => return Level::Debug,
+		_ => return Level::Trace,
+	}
+
+	if let Some(level) = env.and_then(Level::parse) {
+		return level;
+	}
+
+	if dev_mode { Level::Debug } else { Level::Info }
+}
+
+pub fn set_level(level: Level) {
+	LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn level() -> Level {
+	Level::from_u8(LEVEL.load(Ordering::Relaxed))
+}
+
+fn enabled(level: Level) -> bool {
+	self::level() >= level
+}
+
+/// Switches every `ui` function to JSON output. Messages become JSON lines on
+/// stderr so stdout only carries the final result document.
+pub fn set_json(enabled: bool) {
+	JSON.store(enabled, Ordering::Relaxed);
+}
+
+pub fn json_enabled() -> bool {
+	JSON.load(Ordering::Relaxed)
+}
+
+/// When to emit ANSI colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorChoice {
+	Auto = 0,
+	Always = 1,
+	Never = 2,
+}
+
+impl ColorChoice {
+	pub fn parse(value: &str) -> Option<Self> {
+		match value.trim().to_ascii_lowercase().as_str() {
+			"auto" => Some(Self::Auto),
+			"always" => Some(Self::Always),
+			"never" => Some(Self::Never),
+			_ => None,
+		}
+	}
+}
+
+/// Resolves the color choice from `--color`, `NO_COLOR` and
+/// `CLICOLOR_FORCE`. An explicit `always`/`never` flag beats the env vars;
+/// `NO_COLOR` beats `CLICOLOR_FORCE`.
+#[doc(hidden)]
+pub fn resolve_color_choice(
+	flag: Option<ColorChoice>,
+	no_color: Option<&str>,
+	clicolor_force: Option<&str>,
+) -> ColorChoice {
+	match flag {
+		Some(ColorChoice::Always) => return ColorChoice::Always,
+		Some(ColorChoice::Never) => return ColorChoice::Never,
+		_ => {}
+	}
+
+	if no_color.is_some_and(|v| !v.is_empty()) {
+		return ColorChoice::Never;
+	}
+
+	if clicolor_force.is_some_and(|v| !v.is_empty() && v != "0") {
+		return ColorChoice::Always;
+	}
+
+	ColorChoice::Auto
+}
+
+/// Applies the color choice to `ui`, crossterm styling and dialoguer.
+pub fn set_color_choice(choice: ColorChoice) {
+	COLOR.store(choice as u8, Ordering::Relaxed);
+
+	// `ui` decides when to style; stop crossterm from second-guessing it via
+	// NO_COLOR once we have.
+	crossterm::style::force_color_output(choice != ColorChoice::Never);
+	dialoguer::console::set_colors_enabled(stdout_colors());
+	dialoguer::console::set_colors_enabled_stderr(stderr_colors());
+}
+
+fn color_choice() -> ColorChoice {
+	match COLOR.load(Ordering::Relaxed) {
+		1 => ColorChoice::Always,
+		2 => ColorChoice::Never,
+		_ => ColorChoice::Auto,
+	}
+}
+
+pub fn stdout_colors() -> bool {
+	match color_choice() {
+		ColorChoice::Always => true,
+		ColorChoice::Never => false,
+		ColorChoice::Auto => std::io::stdout().is_terminal(),
+	}
+}
+
+pub fn stderr_colors() -> bool {
+	match color_choice() {
+		ColorChoice::Always => true,
+		ColorChoice::Never => false,
+		ColorChoice::Auto => std::io::stderr().is_terminal(),
+	}
+}
+
+/// Allows or forbids prompts. Off with `--non-interactive` or when stdin is
+/// not a terminal.
+pub fn set_interactive(enabled: bool) {
+	INTERACTIVE.store(enabled, Ordering::Relaxed);
+}
+
+pub fn interactive() -> bool {
+	INTERACTIVE.load(Ordering::Relaxed)
+}
+
+/// Call before prompting for `what`: fails with a usage error naming
+/// `instead` (the flag or argument that supplies it) when prompts are off.
+pub fn require_interactive(what: &str, instead: &str) -> anyhow::Result<()> {
+	if interactive() {
+		return Ok(());
+	}
+	Err(crate::error::prompt_unavailable(what, instead))
+}
+
+/// Returns the dialoguer theme matching the color choice. Prompts render on
+/// stderr, so that stream decides.
+pub fn theme() -> Box<dyn dialoguer::theme::Theme> {
+	if stderr_colors() {
+		Box::new(dialoguer::theme::ColorfulTheme::default())
+	} else {
+		Box::new(dialoguer::theme::SimpleTheme)
+	}
 }
 
-fn stderr_colors() -> bool {
-	std::io::stderr().is_terminal()
+/// Emits a structured event as a JSON line on stderr. No-op outside JSON mode.
+pub fn event(name: &str, fields: serde_json::Value) {
+	if !json_enabled() {
+		return;
+	}
+
+	let mut line = serde_json::json!({ "event": name });
+	if let (Some(line), serde_json::Value::Object(fields)) =
+		(line.as_object_mut(), fields)
+	{
+		line.extend(fields);
+	}
+	eprintln!("{line}");
+}
+
+/// Records the command's final result. In JSON mode `main` prints it on
+/// stdout once the command returns.
+pub fn result(value: serde_json::Value) {
+	if let Ok(mut slot) = RESULT.lock() {
+		*slot = Some(value);
+	}
+}
+
+/// Takes the result recorded by [`result`], if any.
+pub fn take_result() -> Option<serde_json::Value> {
+	RESULT.lock().ok().and_then(|mut slot| slot.take())
+}
+
+fn json_message(level: &str, message: &str) {
+	event(
+		"message",
+		serde_json::json!({ "level": level, "message": message }),
+	);
 }
 
 pub fn info(message: &str) {
-	if stdout_colors() {
+	if !enabled(Level::Info) {
+		return;
+	}
+
+	if json_enabled() {
+		json_message("info", message);
+	} else if stdout_colors() {
 		println!("{} {}", "→".cyan(), message.cyan());
 	} else {
 		println!("{message}");
@@ -19,7 +252,13 @@ pub fn info(message: &str) {
 }
 
 pub fn success(message: &str) {
-	if stdout_colors() {
+	if !enabled(Level::Info) {
+		return;
+	}
+
+	if json_enabled() {
+		json_message("success", message);
+	} else if stdout_colors() {
 		println!("{} {}", "✓".green(), message.green());
 	} else {
 		println!("{message}");
@@ -27,7 +266,13 @@ pub fn success(message: &str) {
 }
 
 pub fn warning(message: &str) {
-	if stdout_colors() {
+	if !enabled(Level::Warn) {
+		return;
+	}
+
+	if json_enabled() {
+		json_message("warning", message);
+	} else if stdout_colors() {
 		println!("{} {}", "!".yellow(), message.yellow());
 	} else {
 		println!("{message}");
@@ -35,25 +280,105 @@ pub fn warning(message: &str) {
 }
 
 pub fn muted(message: &str) {
-	if stdout_colors() {
+	if !enabled(Level::Info) {
+		return;
+	}
+
+	if json_enabled() {
+		json_message("muted", message);
+	} else if stdout_colors() {
 		println!("{}", message.dark_grey());
 	} else {
 		println!("{message}");
 	}
 }
 
+/// Prints an action `--dry-run` skipped. Shown at every level: with `-q` the
+/// plan is all a dry run prints.
+pub fn plan(message: &str) {
+	if json_enabled() {
+		json_message("plan", message);
+	} else if stdout_colors() {
+		println!("{} {}", "→".cyan(), message.cyan());
+	} else {
+		println!("{message}");
+	}
+}
+
+/// Prints `text` on stdout as it is, for output the command formats itself.
+/// Like [`info`], hidden at `-q`; in JSON mode it only belongs in the result.
+pub fn text(text: &str) {
+	if !enabled(Level::Info) || json_enabled() {
+		return;
+	}
+	print!("{text}");
+}
+
+/// Redraws `line` in place on stdout, as a progress indicator. Hidden at `-q`
+/// and in JSON mode, where progress is reported through events.
+pub fn progress(line: &str) -> std::io::Result<()> {
+	if !enabled(Level::Info) || json_enabled() {
+		return Ok(());
+	}
+	let mut out = std::io::stdout();
+	write!(out, "\r{line}")?;
+	out.flush()
+}
+
+/// Moves past the line drawn by [`progress`].
+pub fn progress_done() {
+	if !enabled(Level::Info) || json_enabled() {
+		return;
+	}
+	println!();
+}
+
 pub fn error(message: &str) {
-	if stderr_colors() {
+	if json_enabled() {
+		json_message("error", message);
+	} else if stderr_colors() {
 		eprintln!("{} {}", "✗".red().bold(), message.red().bold());
 	} else {
 		eprintln!("{message}");
 	}
 }
 
+/// Prints how to fix the error just reported. Shown at every level, like
+/// [`error`].
+pub fn hint(message: &str) {
+	if json_enabled() {
+		json_message("hint", message);
+	} else if stderr_colors() {
+		eprintln!("{} {}", "hint:".cyan().bold(), message);
+	} else {
+		eprintln!("hint: {message}");
+	}
+}
+
 pub fn debug(message: &str) {
-	if stderr_colors() {
+	if !enabled(Level::Debug) {
+		return;
+	}
+
+	if json_enabled() {
+		json_message("debug", message);
+	} else if stderr_colors() {
 		eprintln!("{} {}", "dbg".dark_grey(), message.dark_grey());
 	} else {
 		eprintln!("[dbg] {message}");
 	}
 }
+
+pub fn trace(message: &str) {
+	if !enabled(Level::Trace) {
+		return;
+	}
+
+	if json_enabled() {
+		json_message("trace", message);
+	} else if stderr_colors() {
+		eprintln!("{} {}", "trc".dark_grey(), message.dark_grey());
+	} else {
+		eprintln!("[trc] {message}");
+	}
+}
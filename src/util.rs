@@ -1,14 +1,77 @@
 use std::process::{Command, ExitStatus, Stdio};
+use std::time::Instant;
+
+use crate::error::{Category, Error};
+use crate::runlog;
+use crate::timings::{self, Kind};
+use crate::ui;
+
+/// Stdout for child processes: inherited normally, redirected to stderr in
+/// JSON mode so stdout only carries the result document.
+pub fn child_stdout() -> Stdio {
+	if ui::json_enabled() {
+		Stdio::from(std::io::stderr())
+	} else {
+		Stdio::inherit()
+	}
+}
+
+/// Renders a command line for logs, quoting arguments that contain spaces.
+pub fn format_command_line(program: &str, args: &[&str]) -> String {
+	std::iter::once(program)
+		.chain(args.iter().copied())
+		.map(|part| {
+			if part.is_empty() || part.contains(char::is_whitespace) {
+				format!("'{}'", escape_sh_single_quoted(part))
+			} else {
+				part.to_string()
+			}
+		})
+		.collect::<Vec<_>>()
+		.join(" ")
+}
+
+fn trace_spawn(
+	program: &str,
+	args: &[&str],
+	dir: Option<&std::path::Path>,
+) -> Instant {
+	let line = format_command_line(program, args);
+	match dir {
+		Some(dir) => ui::trace(&format!("run: {line} (in {})", dir.display())),
+		None => ui::trace(&format!("run: {line}")),
+	}
+	Instant::now()
+}
+
+fn process_span(program: &str, args: &[&str]) -> timings::Guard {
+	timings::span(Kind::Process, format_command_line(program, args))
+}
+
+fn trace_exit(
+	program: &str,
+	args: &[&str],
+	dir: Option<&std::path::Path>,
+	started: Instant,
+	status: &ExitStatus,
+) {
+	ui::trace(&format!("exit: {program} → {status}"));
+	runlog::process(program, args, dir, status.code(), started);
+}
 
 /// Runs a command inheriting stdin/stdout/stderr.
 pub fn run_inherit(program: &str, args: &[&str]) -> anyhow::Result<ExitStatus> {
 	let mut cmd = Command::new(program);
 	cmd.args(args)
 		.stdin(Stdio::inherit())
-		.stdout(Stdio::inherit())
+		.stdout(child_stdout())
 		.stderr(Stdio::inherit());
 
-	Ok(cmd.status()?)
+	let _span = process_span(program, args);
+	let started = trace_spawn(program, args, None);
+	let status = cmd.status()?;
+	trace_exit(program, args, None, started, &status);
+	Ok(status)
 }
 
 /// Runs a command inheriting stdin/stdout/stderr from a specific working dir.
@@ -21,26 +84,109 @@ pub fn run_inherit_with_dir(
 	cmd.current_dir(current_dir)
 		.args(args)
 		.stdin(Stdio::inherit())
-		.stdout(Stdio::inherit())
+		.stdout(child_stdout())
 		.stderr(Stdio::inherit());
 
-	Ok(cmd.status()?)
+	let _span = process_span(program, args);
+	let started = trace_spawn(program, args, Some(current_dir));
+	let status = cmd.status()?;
+	trace_exit(program, args, Some(current_dir), started, &status);
+	Ok(status)
 }
 
 /// Runs a command and returns stdout as UTF-8. If it fails, includes stderr in
 /// the error message.
 pub fn run_capture(program: &str, args: &[&str]) -> anyhow::Result<String> {
+	let _span = process_span(program, args);
+	let started = trace_spawn(program, args, None);
 	let out = Command::new(program).args(args).output()?;
+	trace_exit(program, args, None, started, &out.status);
 	if !out.status.success() {
-		let stderr = String::from_utf8_lossy(&out.stderr).trim().to_string();
-		anyhow::bail!(
+		let stderr = String::from_utf8_lossy(&out.stderr);
+		return Err(capture_failed(program, args, stderr.trim()));
+	}
+	Ok(String::from_utf8_lossy(&out.stdout).trim().to_string())
+}
+
+/// The error [`run_capture`] returns for a non-zero exit.
+pub(crate) fn capture_failed(
+	program: &str,
+	args: &[&str],
+	detail: &str,
+) -> anyhow::Error {
+	Error::new(
+		Category::ExternalProcess,
+		format!(
 			"command failed: {} {} ({})",
 			program,
 			args.join(" "),
-			stderr
-		);
+			detail
+		),
+	)
+	.into()
+}
+
+/// Runs a command and returns stdout followed by stderr, whatever the exit
+/// status. For tools like `java -version` that report on stderr.
+pub fn run_capture_combined(
+	program: &str,
+	args: &[&str],
+) -> anyhow::Result<String> {
+	let _span = process_span(program, args);
+	let started = trace_spawn(program, args, None);
+	let out = Command::new(program).args(args).output()?;
+	trace_exit(program, args, None, started, &out.status);
+	let mut text = String::from_utf8_lossy(&out.stdout).into_owned();
+	text.push_str(&String::from_utf8_lossy(&out.stderr));
+	Ok(text.trim().to_string())
+}
+
+/// Splits a command line into words, honoring single and double quotes and
+/// backslash escapes outside single quotes.
+pub fn split_command_line(line: &str) -> anyhow::Result<Vec<String>> {
+	let mut words = Vec::new();
+	let mut current = String::new();
+	let mut in_word = false;
+	let mut quote: Option<char> = None;
+	let mut chars = line.chars();
+
+	while let Some(c) = chars.next() {
+		match (quote, c) {
+			(Some(q), c) if c == q => quote = None,
+			(Some('\''), c) => current.push(c),
+			(_, '\\') => {
+				let escaped = chars.next().ok_or_else(|| {
+					anyhow::anyhow!("trailing backslash in: {line}")
+				})?;
+				current.push(escaped);
+				in_word = true;
+			}
+			(Some(_), c) => current.push(c),
+			(None, '\'' | '"') => {
+				quote = Some(c);
+				in_word = true;
+			}
+			(None, c) if c.is_whitespace() => {
+				if in_word {
+					words.push(std::mem::take(&mut current));
+					in_word = false;
+				}
+			}
+			(None, c) => {
+				current.push(c);
+				in_word = true;
+			}
+		}
 	}
-	Ok(String::from_utf8_lossy(&out.stdout).trim().to_string())
+
+	if quote.is_some() {
+		anyhow::bail!("unterminated quote in: {line}");
+	}
+	if in_word {
+		words.push(current);
+	}
+
+	Ok(words)
 }
 
 /// Returns the Levenshtein edit distance between two strings.
@@ -72,6 +218,38 @@ pub fn levenshtein(a: &str, b: &str) -> usize {
 	previous[n]
 }
 
+/// Returns the candidate `input` most likely meant, if any is close enough.
+///
+/// Comparison ignores case. A case-insensitive exact match wins, then a
+/// candidate that starts with `input`, then the smallest edit distance.
+pub fn closest<'a>(
+	input: &str,
+	candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+	let input = input.to_lowercase();
+	candidates
+		.into_iter()
+		.filter_map(|c| {
+			let candidate = c.to_lowercase();
+			let rank = if candidate == input {
+				0
+			} else if !input.is_empty() && candidate.starts_with(&input) {
+				1
+			} else {
+				let distance = levenshtein(&input, &candidate);
+				if distance > MAX_SUGGESTION_DISTANCE {
+					return None;
+				}
+				1 + distance
+			};
+			Some((rank, c))
+		})
+		.min_by_key(|(rank, _)| *rank)
+		.map(|(_, c)| c)
+}
+
+const MAX_SUGGESTION_DISTANCE: usize = 3;
+
 /// Escapes a string for use inside a POSIX single-quoted shell string.
 /// Single quotes cannot appear inside a single-quoted string, so the string
 /// is terminated, a literal `'` is inserted with `'\''`, then reopened.
@@ -83,11 +261,13 @@ pub fn escape_sh_single_quoted(value: &str) -> String {
 /// given command string. stdout/stderr/stdin are all redirected to /dev/null
 /// so the process runs silently in the background.
 pub fn spawn_shell_background(command: &str) -> anyhow::Result<()> {
+	let started = trace_spawn("sh", &["-c", command], None);
 	Command::new("sh")
 		.args(["-c", command])
 		.stdout(Stdio::null())
 		.stderr(Stdio::null())
 		.stdin(Stdio::null())
 		.spawn()?;
+	runlog::process("sh", &["-c", command], None, None, started);
 	Ok(())
 }
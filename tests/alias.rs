@@ -0,0 +1,147 @@
+#![allow(clippy::unwrap_used)]
+
+mod common;
+
+use std::ffi::OsString;
+
+use eagle::alias::{Aliases, expand, validate};
+use eagle::cli::build_cli;
+use eagle::config::Config;
+use eagle::util::split_command_line;
+use predicates::str::contains;
+use tempfile::TempDir;
+
+fn aliases(toml: &str) -> Aliases {
+	let tmp = TempDir::new().unwrap();
+	let path = tmp.path().join("config.toml");
+	std::fs::write(&path, toml).unwrap();
+	Aliases::load(&Config::load(&path).unwrap(), &build_cli())
+}
+
+fn args(words: &[&str]) -> Vec<OsString> {
+	words.iter().map(OsString::from).collect()
+}
+
+#[test]
+fn split_honors_quotes_and_escapes() {
+	assert_eq!(
+		split_command_line(r#"minecraft create --motd "hi there" --name a\ b"#)
+			.unwrap(),
+		["minecraft", "create", "--motd", "hi there", "--name", "a b"]
+	);
+	assert_eq!(split_command_line("a '' b").unwrap(), ["a", "", "b"]);
+	assert!(split_command_line("a 'b").is_err());
+}
+
+#[test]
+fn expands_alias_and_keeps_trailing_args() {
+	let aliases = aliases("[alias]\nmcs = \"minecraft create --type paper\"\n");
+	let out = expand(args(&["eagle", "mcs", "--name", "x"]), &aliases);
+	assert_eq!(
+		out,
+		args(&[
+			"eagle",
+			"minecraft",
+			"create",
+			"--type",
+			"paper",
+			"--name",
+			"x"
+		])
+	);
+}
+
+#[test]
+fn expands_after_leading_global_flags() {
+	let aliases = aliases("[alias]\nv2 = \"version\"\n");
+	let out =
+		expand(args(&["eagle", "--color", "never", "-q", "v2"]), &aliases);
+	assert_eq!(out, args(&["eagle", "--color", "never", "-q", "version"]));
+}
+
+#[test]
+fn non_alias_args_are_untouched() {
+	let aliases = aliases("[alias]\nmcs = \"minecraft create\"\n");
+	let input = args(&["eagle", "version", "mcs"]);
+	assert_eq!(expand(input.clone(), &aliases), input);
+}
+
+#[test]
+fn aliases_cannot_shadow_builtins() {
+	let cli = build_cli();
+	assert!(validate("update", "version", &cli).is_err());
+	assert!(validate("u", "version", &cli).is_err());
+	assert!(validate("x", "notacommand", &cli).is_err());
+	assert!(validate("x", "", &cli).is_err());
+	assert!(validate("x", "minecraft create", &cli).is_ok());
+
+	let loaded = aliases("[alias]\nversion = \"update\"\nok = \"version\"\n");
+	assert_eq!(loaded.entries.len(), 1);
+	assert_eq!(loaded.rejected.len(), 1);
+}
+
+#[test]
+fn aliases_run_and_show_in_help() {
+	let tmp = TempDir::new().unwrap();
+	let path = tmp.path().join("config.toml");
+
+	common::eagle()
+		.env("EAGLE_CONFIG", &path)
+		.args(["config", "set", "alias.ver", "version"])
+		.assert()
+		.success();
+
+	common::eagle()
+		.env("EAGLE_CONFIG", &path)
+		.arg("ver")
+		.assert()
+		.success()
+		.stdout(contains(env!("CARGO_PKG_VERSION")));
+
+	common::eagle()
+		.env("EAGLE_CONFIG", &path)
+		.arg("help")
+		.assert()
+		.success()
+		.stdout(contains("User aliases"))
+		.stdout(contains("ver"));
+}
+
+#[test]
+fn config_set_rejects_colliding_alias() {
+	let tmp = TempDir::new().unwrap();
+	common::eagle()
+		.env("EAGLE_CONFIG", tmp.path().join("config.toml"))
+		.args(["config", "set", "alias.update", "version"])
+		.assert()
+		.failure();
+}
+
+#[test]
+fn a_bad_alias_table_does_not_break_clap() {
+	let tmp = TempDir::new().unwrap();
+	let path = tmp.path().join("config.toml");
+	std::fs::write(&path, "alias = \"oops\"\n").unwrap();
+
+	for arg in ["--help", "--version"] {
+		common::eagle()
+			.env("EAGLE_CONFIG", &path)
+			.arg(arg)
+			.assert()
+			.success();
+	}
+
+	common::eagle()
+		.env("EAGLE_CONFIG", &path)
+		.arg("--bogus")
+		.assert()
+		.code(2)
+		.stderr(contains("--bogus"));
+
+	common::eagle()
+		.env("EAGLE_CONFIG", &path)
+		.arg("version")
+		.assert()
+		.success()
+		.stdout(contains("Ignoring alias 'alias': must be a table"));
+}
@@ -0,0 +1,84 @@
+#![allow(clippy::unwrap_used)]
+
+mod common;
+
+use std::time::{Duration, Instant};
+
+use common::{Response, StandIn};
+use eagle::cleanup;
+use tempfile::TempDir;
+
+#[test]
+fn run_removes_only_registered_paths() {
+	let tmp = TempDir::new().unwrap();
+	let file = tmp.path().join("server.jar.part");
+	let dir = tmp.path().join("lobby");
+	let kept = tmp.path().join("kept");
+	std::fs::write(&file, "partial").unwrap();
+	std::fs::create_dir_all(dir.join("world")).unwrap();
+	std::fs::create_dir_all(&kept).unwrap();
+
+	drop(cleanup::register(&kept));
+	assert!(!cleanup::pending().contains(&kept));
+
+	let _file = cleanup::register(&file);
+	let _dir = cleanup::register(&dir);
+	cleanup::run();
+
+	assert!(!file.exists());
+	assert!(!dir.exists());
+	assert!(kept.exists());
+	assert!(cleanup::pending().is_empty());
+}
+
+/// Serves a Paper build list, then a jar download that stalls after the
+/// first chunk.
+fn stalling_paper_api() -> StandIn {
+	let paper = StandIn::new();
+	let builds = format!(
+		r#"[{{"id":1,"channel":"STABLE","downloads":{{"server:default":{{"name":"paper.jar","checksums":{{"sha256":"{}"}},"url":"{}/paper.jar"}}}}}}]"#,
+		"0".repeat(64),
+		paper.url
+	);
+	paper.serve(move |path| {
+		if path.contains("/builds") {
+			Response::ok(builds.clone())
+		} else {
+			Response::ok("x".repeat(4096)).stall()
+		}
+	})
+}
+
+#[cfg(unix)]
+#[test]
+fn interrupt_removes_half_created_server() {
+	let tmp = TempDir::new().unwrap();
+	let server = tmp.path().join("lobby");
+	let paper = stalling_paper_api();
+	let mut child = std::process::Command::new(env!("CARGO_BIN_EXE_eagle"))
+		.env("EAGLE_HOME", tmp.path())
+		.env("EAGLE_MINECRAFT_SERVERS_ROOT", tmp.path())
+		.env("EAGLE_PAPER_API", &paper.url)
+		.args(["--json", "minecraft", "create", "--name", "lobby"])
+		.args(["--type", "paper", "--version", "1.21.11"])
+		.stdout(std::process::Stdio::null())
+		.stderr(std::process::Stdio::null())
+		.spawn()
+		.unwrap();
+
+	let part = server.join("server.jar.part");
+	let deadline = Instant::now() + Duration::from_secs(20);
+	while !part.exists() && Instant::now() < deadline {
+		std::thread::sleep(Duration::from_millis(20));
+	}
+	assert!(part.exists(), "download never started");
+
+	std::process::Command::new("kill")
+		.args(["-INT", &child.id().to_string()])
+		.status()
+		.unwrap();
+	let status = child.wait().unwrap();
+
+	assert_eq!(status.code(), Some(130));
+	assert!(!server.exists());
+}
@@ -1,15 +1,19 @@
-use assert_cmd::cargo::cargo_bin_cmd;
+#![allow(clippy::unwrap_used)]
+
+mod common;
+
+use predicates::prelude::PredicateBooleanExt;
 use predicates::str::contains;
 
 #[test]
 fn help_command_succeeds() {
-	let mut cmd = cargo_bin_cmd!("eagle");
+	let mut cmd = common::eagle();
 	cmd.arg("help").assert().success().stdout(contains("eagle"));
 }
 
 #[test]
 fn version_command_succeeds() {
-	let mut cmd = cargo_bin_cmd!("eagle");
+	let mut cmd = common::eagle();
 	cmd.arg("version")
 		.assert()
 		.success()
@@ -18,7 +22,7 @@ fn version_command_succeeds() {
 
 #[test]
 fn minecraft_create_help_succeeds() {
-	let mut cmd = cargo_bin_cmd!("eagle");
+	let mut cmd = common::eagle();
 	cmd.args(["minecraft", "create", "--help"])
 		.assert()
 		.success()
@@ -27,7 +31,7 @@ fn minecraft_create_help_succeeds() {
 
 #[test]
 fn create_help_succeeds() {
-	let mut cmd = cargo_bin_cmd!("eagle");
+	let mut cmd = common::eagle();
 	cmd.args(["create", "--help"])
 		.assert()
 		.success()
@@ -36,7 +40,7 @@ fn create_help_succeeds() {
 
 #[test]
 fn update_help_succeeds() {
-	let mut cmd = cargo_bin_cmd!("eagle");
+	let mut cmd = common::eagle();
 	cmd.args(["update", "--help"])
 		.assert()
 		.success()
@@ -45,16 +49,16 @@ fn update_help_succeeds() {
 
 #[test]
 fn removed_commands_fail() {
-	let mut goto_cmd = cargo_bin_cmd!("eagle");
+	let mut goto_cmd = common::eagle();
 	goto_cmd.arg("goto").assert().failure();
 
-	let mut init_cmd = cargo_bin_cmd!("eagle");
+	let mut init_cmd = common::eagle();
 	init_cmd.arg("init").assert().failure();
 }
 
 #[test]
 fn minecraft_help_shows_ram_flag() {
-	let mut cmd = cargo_bin_cmd!("eagle");
+	let mut cmd = common::eagle();
 	cmd.args(["minecraft", "--help"])
 		.assert()
 		.success()
@@ -63,7 +67,7 @@ fn minecraft_help_shows_ram_flag() {
 
 #[test]
 fn help_flag_succeeds() {
-	let mut cmd = cargo_bin_cmd!("eagle");
+	let mut cmd = common::eagle();
 	cmd.arg("--help")
 		.assert()
 		.success()
@@ -72,15 +76,143 @@ fn help_flag_succeeds() {
 
 #[test]
 fn unknown_subcommand_fails() {
-	let mut cmd = cargo_bin_cmd!("eagle");
+	let mut cmd = common::eagle();
 	cmd.arg("notacommand").assert().failure();
 }
 
 #[test]
 fn version_output_contains_version_number() {
-	let mut cmd = cargo_bin_cmd!("eagle");
+	let mut cmd = common::eagle();
 	cmd.arg("version")
 		.assert()
 		.success()
 		.stdout(contains(env!("CARGO_PKG_VERSION")));
 }
+
+#[test]
+fn version_flag_is_capital_v() {
+	let mut cmd = common::eagle();
+	cmd.arg("-V")
+		.assert()
+		.success()
+		.stdout(contains(env!("CARGO_PKG_VERSION")));
+}
+
+#[test]
+fn quiet_suppresses_output() {
+	let mut cmd = common::eagle();
+	cmd.args(["-q", "version"])
+		.assert()
+		.success()
+		.stdout(predicates::str::is_empty())
+		.stderr(predicates::str::is_empty());
+}
+
+#[test]
+fn trace_level_logs_subprocesses() {
+	let tmp = tempfile::TempDir::new().unwrap();
+	let mut cmd = common::eagle();
+	cmd.env("EAGLE_LOG", "trace")
+		.env("EDITOR", "true")
+		.env("EAGLE_CONFIG", tmp.path().join("config.toml"))
+		.args(["config", "edit"])
+		.assert()
+		.success()
+		.stderr(contains("run: true"))
+		.stderr(contains("exit: true"));
+}
+
+#[test]
+fn color_always_emits_ansi_when_piped() {
+	let mut cmd = common::eagle();
+	cmd.args(["--color=always", "version"])
+		.env("NO_COLOR", "1")
+		.assert()
+		.success()
+		.stdout(contains("\u{1b}["));
+}
+
+#[test]
+fn no_color_env_disables_forced_colors() {
+	let mut cmd = common::eagle();
+	cmd.arg("version")
+		.env("NO_COLOR", "1")
+		.env("CLICOLOR_FORCE", "1")
+		.assert()
+		.success()
+		.stdout(contains("\u{1b}[").not());
+}
+
+#[test]
+fn help_follows_nested_paths_and_shows_examples() {
+	let mut cmd = common::eagle();
+	cmd.args(["help", "minecraft", "create"])
+		.assert()
+		.success()
+		.stdout(contains("Usage: eagle minecraft create"))
+		.stdout(contains("Examples:"))
+		.stdout(contains("eagle minecraft create --name lobby"));
+}
+
+#[test]
+fn help_keeps_subcommand_examples_out_of_the_parent() {
+	let mut cmd = common::eagle();
+	cmd.args(["help", "m"])
+		.assert()
+		.success()
+		.stdout(contains("Pick a server and start it"))
+		.stdout(contains("eagle minecraft start lobby").not())
+		.stdout(contains("eagle minecraft create --name").not());
+
+	common::eagle()
+		.args(["help", "m", "start"])
+		.assert()
+		.success()
+		.stdout(contains("eagle minecraft start lobby --ram-mb 4096"));
+}
+
+#[test]
+fn help_suggests_the_closest_nested_command() {
+	let mut cmd = common::eagle();
+	cmd.args(["help", "minecraft", "crate"])
+		.assert()
+		.code(2)
+		.stderr(contains("Did you mean: minecraft create?"));
+}
+
+#[test]
+fn root_help_groups_commands_by_category() {
+	let mut cmd = common::eagle();
+	cmd.arg("--help")
+		.assert()
+		.success()
+		.stdout(contains("Workflows:\n  create"))
+		.stdout(contains("Self-management:"))
+		.stdout(contains("Diagnostics:"))
+		.stdout(contains("eaglecord-dev").not())
+		.stdout(contains("docs ").not());
+}
+
+#[test]
+fn root_help_marks_commands_whose_tools_are_missing() {
+	let empty = tempfile::TempDir::new().unwrap();
+	for args in [&["--help"][..], &["help"]] {
+		common::eagle()
+			.args(args)
+			.env("PATH", empty.path())
+			.assert()
+			.success()
+			.stdout(contains(
+				"Create a new project from a template [needs git, bun]",
+			));
+	}
+}
+
+#[test]
+fn minecraft_create_suggests_a_server_type() {
+	common::eagle()
+		.args(["minecraft", "create", "--name", "lobby", "--type", "papr"])
+		.assert()
+		.code(2)
+		.stderr(contains("a similar value exists: 'paper'"));
+}
@@ -0,0 +1,68 @@
+#![allow(clippy::unwrap_used)]
+
+use std::sync::Arc;
+
+use eagle::commands::{CommandSpec, Platform, iter_specs};
+use eagle::context::Context;
+use eagle::error::{self, Category};
+use eagle::process::Scripted;
+
+fn spec(name: &str) -> &'static CommandSpec {
+	iter_specs().into_iter().find(|s| s.name == name).unwrap()
+}
+
+#[test]
+fn platforms_limit_where_a_command_runs() {
+	let uninstall = spec("uninstall");
+	assert!(uninstall.supports(Some(Platform::MacOs)));
+	assert!(uninstall.supports(Some(Platform::Linux)));
+	assert!(!uninstall.supports(Some(Platform::Windows)));
+	assert!(!uninstall.supports(None));
+
+	// `update --list` works everywhere; only installing is limited.
+	assert!(spec("update").supports(Some(Platform::Windows)));
+
+	// No platforms listed means everywhere.
+	assert!(spec("config").supports(Some(Platform::Windows)));
+	assert!(spec("config").supports(None));
+}
+
+#[test]
+fn marks_list_missing_tools() {
+	let runner = Scripted::new().missing("git").missing("bun");
+	assert_eq!(spec("create").marks(Some(&runner)), ["needs git, bun"]);
+	assert!(spec("create").marks(Some(&Scripted::new())).is_empty());
+	assert!(spec("create").marks(None).is_empty());
+}
+
+#[test]
+fn check_rejects_a_missing_tool_before_running() {
+	let runner = Arc::new(Scripted::new().missing("git"));
+	let ctx = Context::new().unwrap().with_processes(runner.clone());
+	let eaglecord = spec("eaglecord");
+	let matches = (eaglecord.command)()
+		.try_get_matches_from(["eaglecord"])
+		.unwrap();
+
+	let err = eaglecord.check(&matches, &ctx).unwrap_err();
+	assert_eq!(error::category(&err), Category::MissingTool);
+	assert!(err.to_string().contains("git"));
+	assert!(runner.calls().is_empty());
+}
+
+#[test]
+fn check_leaves_subcommand_tools_to_the_subcommand() {
+	let runner = Arc::new(Scripted::new().missing("java"));
+	let ctx = Context::new().unwrap().with_processes(runner);
+	let minecraft = spec("minecraft");
+
+	let start = (minecraft.command)()
+		.try_get_matches_from(["minecraft"])
+		.unwrap();
+	assert!(minecraft.check(&start, &ctx).is_err());
+
+	let create = (minecraft.command)()
+		.try_get_matches_from(["minecraft", "create", "--name", "lobby"])
+		.unwrap();
+	assert!(minecraft.check(&create, &ctx).is_ok());
+}
@@ -0,0 +1,167 @@
+#![allow(dead_code, clippy::unwrap_used)]
+
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use assert_cmd::Command;
+use assert_cmd::cargo::cargo_bin_cmd;
+use tempfile::TempDir;
+
+/// `eagle` with `EAGLE_HOME` in a fresh temp dir, so test runs never read
+/// the developer's config or append to their run log. The dir is removed
+/// when the command is dropped.
+pub struct Eagle {
+	cmd: Command,
+	_home: TempDir,
+}
+
+pub fn eagle() -> Eagle {
+	let home = TempDir::new().unwrap();
+	let mut cmd = cargo_bin_cmd!("eagle");
+	cmd.env("EAGLE_HOME", home.path())
+		.env_remove("EAGLE_CONFIG")
+		.env_remove("EAGLE_RUN_LOG");
+	Eagle { cmd, _home: home }
+}
+
+impl Deref for Eagle {
+	type Target = Command;
+
+	fn deref(&self) -> &Command {
+		&self.cmd
+	}
+}
+
+impl DerefMut for Eagle {
+	fn deref_mut(&mut self) -> &mut Command {
+		&mut self.cmd
+	}
+}
+
+/// What [`StandIn`] answers a request with.
+#[derive(Debug, Clone)]
+pub struct Response {
+	status: String,
+	body: String,
+	next: Option<String>,
+	stall: bool,
+}
+
+impl Response {
+	/// `200 OK` with `body`.
+	pub fn ok(body: impl Into<String>) -> Self {
+		Self {
+			status: "200 OK".to_string(),
+			body: body.into(),
+			next: None,
+			stall: false,
+		}
+	}
+
+	pub fn status(mut self, status: &str) -> Self {
+		self.status = status.to_string();
+		self
+	}
+
+	/// Links `url` as the next page, like GitHub's `Link` header.
+	pub fn next_page(mut self, url: impl Into<String>) -> Self {
+		self.next = Some(url.into());
+		self
+	}
+
+	/// Announces a much longer body than `body`, sends `body` and then
+	/// hangs, like a download that stopped making progress.
+	pub fn stall(mut self) -> Self {
+		self.stall = true;
+		self
+	}
+
+	fn write_to(&self, stream: &mut impl Write) {
+		let length = if self.stall {
+			10_000_000
+		} else {
+			self.body.len()
+		};
+		let link = self
+			.next
+			.as_ref()
+			.map(|url| format!("Link: <{url}>; rel=\"next\"\r\n"))
+			.unwrap_or_default();
+		let _ = write!(
+			stream,
+			"HTTP/1.1 {}\r\nConnection: close\r\n{link}\
+Content-Length: {length}\r\n\r\n{}",
+			self.status, self.body
+		);
+		let _ = stream.flush();
+		if self.stall {
+			std::thread::sleep(Duration::from_secs(60));
+		}
+	}
+}
+
+/// A local HTTP server standing in for GitHub, Paper or Fabric until the
+/// test process exits.
+pub struct StandIn {
+	/// `http://127.0.0.1:<port>`, without a trailing slash.
+	pub url: String,
+	listener: TcpListener,
+	requests: Arc<AtomicUsize>,
+}
+
+impl StandIn {
+	pub fn new() -> Self {
+		let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+		let url = format!("http://{}", listener.local_addr().unwrap());
+		Self {
+			url,
+			listener,
+			requests: Arc::new(AtomicUsize::new(0)),
+		}
+	}
+
+	/// Answers each request with `respond(path)`, where `path` includes
+	/// the query string.
+	pub fn serve(
+		self,
+		respond: impl Fn(&str) -> Response + Send + Sync + 'static,
+	) -> Self {
+		let listener = self.listener.try_clone().unwrap();
+		let requests = self.requests.clone();
+		let respond = Arc::new(respond);
+
+		std::thread::spawn(move || {
+			for mut stream in listener.incoming().flatten() {
+				let requests = requests.clone();
+				let respond = respond.clone();
+				std::thread::spawn(move || {
+					let mut buf = [0_u8; 4096];
+					let n = stream.read(&mut buf).unwrap_or(0);
+					let request = String::from_utf8_lossy(
+						buf.get(..n).unwrap_or_default(),
+					)
+					.into_owned();
+					let path = request.split(' ').nth(1).unwrap_or("/");
+					requests.fetch_add(1, Ordering::SeqCst);
+					respond(path).write_to(&mut stream);
+				});
+			}
+		});
+
+		self
+	}
+
+	/// How many requests came in so far.
+	pub fn requests(&self) -> usize {
+		self.requests.load(Ordering::SeqCst)
+	}
+}
+
+/// A [`StandIn`] that answers every request with `response`.
+pub fn stand_in(response: Response) -> StandIn {
+	StandIn::new().serve(move |_| response.clone())
+}
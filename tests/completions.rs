@@ -0,0 +1,89 @@
+#![allow(clippy::unwrap_used)]
+
+mod common;
+
+use predicates::str::contains;
+use tempfile::TempDir;
+
+fn complete(config: &std::path::Path, words: &[&str]) -> String {
+	let output = common::eagle()
+		.env("COMPLETE", "fish")
+		.env("EAGLE_CONFIG", config)
+		.arg("--")
+		.args(words)
+		.output()
+		.unwrap();
+	assert!(output.status.success());
+	String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn registration_scripts_for_every_shell() {
+	for shell in ["bash", "zsh", "fish", "powershell", "elvish"] {
+		common::eagle()
+			.args(["completions", shell])
+			.assert()
+			.success()
+			.stdout(contains("COMPLETE"));
+	}
+}
+
+#[test]
+fn unknown_shell_is_rejected() {
+	common::eagle()
+		.args(["completions", "tcsh"])
+		.assert()
+		.failure();
+}
+
+#[test]
+fn completes_registered_commands() {
+	let tmp = TempDir::new().unwrap();
+	let out = complete(&tmp.path().join("c.toml"), &["eagle", ""]);
+	for name in ["minecraft", "create", "update", "completions", "config"] {
+		assert!(out.lines().any(|l| l.starts_with(name)), "{name}: {out}");
+	}
+}
+
+#[test]
+fn completes_server_types() {
+	let tmp = TempDir::new().unwrap();
+	let out = complete(
+		&tmp.path().join("c.toml"),
+		&["eagle", "minecraft", "create", "--type", ""],
+	);
+	assert!(out.contains("paper"));
+	assert!(out.contains("fabric"));
+}
+
+#[test]
+fn completes_templates_from_config() {
+	let tmp = TempDir::new().unwrap();
+	let config = tmp.path().join("c.toml");
+	std::fs::write(&config, "[templates.vite]\nrepo = \"https://x/v.git\"\n")
+		.unwrap();
+
+	let out = complete(&config, &["eagle", "create", "--template", ""]);
+	assert!(out.contains("discord"));
+	assert!(out.contains("vite"));
+}
+
+#[test]
+fn completes_server_names() {
+	let tmp = TempDir::new().unwrap();
+	let servers = tmp.path().join("servers");
+	std::fs::create_dir_all(servers.join("lobby")).unwrap();
+	std::fs::write(servers.join("lobby").join("eula.txt"), "").unwrap();
+	let config = tmp.path().join("c.toml");
+	std::fs::write(
+		&config,
+		format!(
+			"[minecraft]\nservers_root = {:?}\n",
+			servers.display().to_string()
+		),
+	)
+	.unwrap();
+
+	let out = complete(&config, &["eagle", "minecraft", "start", ""]);
+	assert!(out.lines().any(|l| l == "lobby"), "{out}");
+}
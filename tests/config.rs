@@ -0,0 +1,218 @@
+#![allow(clippy::unwrap_used)]
+
+mod common;
+
+use eagle::commands::create::templates;
+use eagle::config::{Config, key_spec};
+use predicates::prelude::PredicateBooleanExt;
+use predicates::str::contains;
+use tempfile::TempDir;
+
+fn load(dir: &TempDir, text: &str) -> Config {
+	let path = dir.path().join("config.toml");
+	std::fs::write(&path, text).unwrap();
+	Config::load(&path).unwrap()
+}
+
+#[test]
+fn missing_file_is_empty_config() {
+	let tmp = TempDir::new().unwrap();
+	let config = Config::load(&tmp.path().join("nope.toml")).unwrap();
+	assert!(config.entries().is_empty());
+}
+
+#[test]
+fn invalid_toml_is_rejected() {
+	let tmp = TempDir::new().unwrap();
+	let path = tmp.path().join("config.toml");
+	std::fs::write(&path, "minecraft = [").unwrap();
+	assert!(Config::load(&path).is_err());
+}
+
+#[test]
+fn reads_nested_values() {
+	let tmp = TempDir::new().unwrap();
+	let config = load(&tmp, "[minecraft]\nram_mb = 4096\nmotd = \"hi\"\n");
+	assert_eq!(config.u32("minecraft.ram_mb").unwrap(), Some(4096));
+	assert_eq!(
+		config.string("minecraft.motd").unwrap().as_deref(),
+		Some("hi")
+	);
+}
+
+#[test]
+fn wrong_type_is_an_error() {
+	let tmp = TempDir::new().unwrap();
+	let config = load(&tmp, "[minecraft]\nport = \"abc\"\n");
+	assert!(config.u16("minecraft.port").is_err());
+}
+
+#[test]
+fn set_validates_key_and_kind() {
+	let mut config = Config::default();
+	assert!(config.set("minecraft.nope", "1").is_err());
+	assert!(config.set("minecraft.port", "70000").is_err());
+	config.set("minecraft.port", "25565").unwrap();
+	assert_eq!(config.u16("minecraft.port").unwrap(), Some(25565));
+}
+
+#[test]
+fn bool_keys_accept_common_spellings() {
+	let mut config = Config::default();
+	assert!(config.set("update.notify", "maybe").is_err());
+	config.set("update.notify", "off").unwrap();
+	assert_eq!(config.bool("update.notify").unwrap(), Some(false));
+	config.set("update.notify", "true").unwrap();
+	assert_eq!(config.bool("update.notify").unwrap(), Some(true));
+}
+
+#[test]
+fn unset_prunes_empty_tables() {
+	let mut config = Config::default();
+	config
+		.set("templates.vite.repo", "https://x/vite.git")
+		.unwrap();
+	assert!(config.unset("templates.vite.repo"));
+	assert!(config.get("templates").is_none());
+	assert!(!config.unset("templates.vite.repo"));
+}
+
+#[test]
+fn save_round_trips() {
+	let tmp = TempDir::new().unwrap();
+	let path = tmp.path().join("nested").join("config.toml");
+	let mut config = Config::load(&path).unwrap();
+	config.set("create.root", "/tmp/projects").unwrap();
+	config.save().unwrap();
+
+	let reloaded = Config::load(&path).unwrap();
+	assert_eq!(
+		reloaded.get("create.root").and_then(|v| v.as_str()),
+		Some("/tmp/projects")
+	);
+}
+
+#[test]
+fn wildcard_keys_match_one_segment() {
+	assert!(key_spec("templates.vite.repo").is_some());
+	assert!(key_spec("templates.repo").is_none());
+	assert!(key_spec("templates.a.b.repo").is_none());
+}
+
+#[test]
+fn config_templates_extend_and_override_builtins() {
+	let tmp = TempDir::new().unwrap();
+	let config = load(
+		&tmp,
+		"[templates.vite]\nrepo = \"https://x/vite.git\"\n\n\
+		 [templates.next]\nrepo = \"https://x/next.git\"\ndir = \"web\"\n",
+	);
+
+	let all = templates(&config).unwrap();
+	let vite = all.iter().find(|t| t.name == "vite").unwrap();
+	assert_eq!(vite.dir, "vite");
+	let next = all.iter().find(|t| t.name == "next").unwrap();
+	assert_eq!(next.repo, "https://x/next.git");
+	assert_eq!(next.dir, "web");
+	assert!(all.iter().any(|t| t.name == "discord"));
+}
+
+#[test]
+fn config_template_names_keep_their_case_for_lookup() {
+	let tmp = TempDir::new().unwrap();
+	let config = load(
+		&tmp,
+		"[templates.MyTpl]\nrepo = \"https://x/my.git\"\ndir = \"Mine\"\n",
+	);
+
+	let all = templates(&config).unwrap();
+	let mine = all.iter().find(|t| t.name == "mytpl").unwrap();
+	assert_eq!(mine.repo, "https://x/my.git");
+	assert_eq!(mine.dir, "Mine");
+}
+
+#[test]
+fn config_command_sets_and_gets() {
+	let tmp = TempDir::new().unwrap();
+	let path = tmp.path().join("config.toml");
+
+	common::eagle()
+		.env("EAGLE_CONFIG", &path)
+		.args(["config", "set", "minecraft.ram_mb", "2048"])
+		.assert()
+		.success();
+
+	common::eagle()
+		.env("EAGLE_CONFIG", &path)
+		.args(["config", "get", "minecraft.ram_mb"])
+		.assert()
+		.success()
+		.stdout(contains("2048"));
+}
+
+#[test]
+fn broken_config_file_is_ignored_but_not_overwritten() {
+	let tmp = TempDir::new().unwrap();
+	let path = tmp.path().join("config.toml");
+	std::fs::write(&path, "minecraft = [").unwrap();
+
+	for args in [&["--help"][..], &["config", "path"]] {
+		common::eagle()
+			.env("EAGLE_CONFIG", &path)
+			.args(args)
+			.assert()
+			.success();
+	}
+
+	common::eagle()
+		.env("EAGLE_CONFIG", &path)
+		.args(["config", "list"])
+		.assert()
+		.success()
+		.stdout(contains("Ignoring the config file"));
+
+	common::eagle()
+		.env("EAGLE_CONFIG", &path)
+		.args(["-q", "config", "list"])
+		.assert()
+		.success()
+		.stdout(contains("Ignoring").not())
+		.stderr("");
+
+	common::eagle()
+		.env("EAGLE_CONFIG", &path)
+		.args(["config", "set", "minecraft.ram_mb", "2048"])
+		.assert()
+		.failure()
+		.stderr(contains("config edit"));
+	assert_eq!(std::fs::read_to_string(&path).unwrap(), "minecraft = [");
+}
+
+#[test]
+fn flags_beat_config_and_config_beats_defaults() {
+	let create = |port: Option<&str>| {
+		let tmp = TempDir::new().unwrap();
+		let mut cmd = common::eagle();
+		cmd.env("EAGLE_MINECRAFT_SERVERS_ROOT", tmp.path())
+			.env("EAGLE_MINECRAFT_PORT", "25580")
+			.args(["--json", "minecraft", "create", "--name", "lobby"])
+			.args(["--type", "fabric", "--version", "1.21.11"])
+			.args(["--skip-download"]);
+		if let Some(port) = port {
+			cmd.args(["--port", port]);
+		}
+		let output = cmd.output().unwrap();
+		let doc: serde_json::Value =
+			serde_json::from_slice(&output.stdout).unwrap();
+		doc.get("result").cloned().unwrap()
+	};
+
+	let configured = create(None);
+	assert_eq!(configured.get("port"), Some(&serde_json::json!(25580)));
+	assert_eq!(
+		configured.get("motd"),
+		Some(&serde_json::json!("eagle minecraft server"))
+	);
+	let flagged = create(Some("25570"));
+	assert_eq!(flagged.get("port"), Some(&serde_json::json!(25570)));
+}
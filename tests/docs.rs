@@ -0,0 +1,87 @@
+#![allow(clippy::unwrap_used)]
+
+mod common;
+
+use eagle::cli::build_cli;
+use eagle::commands::docs::{render_man, render_markdown};
+use tempfile::TempDir;
+
+fn page<'a>(pages: &'a [(String, String)], name: &str) -> &'a str {
+	pages
+		.iter()
+		.find(|(file, _)| file == name)
+		.map(|(_, content)| content.as_str())
+		.unwrap()
+}
+
+#[test]
+fn markdown_has_one_page_per_command_including_nested() {
+	let pages = render_markdown(build_cli());
+	let create = page(&pages, "eagle-minecraft-create.md");
+	assert!(create.starts_with("# eagle minecraft create"));
+	assert!(create.contains("--skip-download"));
+	assert!(create.contains("`paper`"));
+}
+
+#[test]
+fn markdown_shows_defaults_that_config_can_override() {
+	let pages = render_markdown(build_cli());
+	let create = page(&pages, "eagle-minecraft-create.md");
+	assert!(create.contains("| Server port | `22222` |"));
+	assert!(create.contains("| Server motd | `eagle minecraft server` |"));
+	assert!(
+		page(&pages, "eagle-minecraft.md").contains("| RAM in MB | `8192` |")
+	);
+}
+
+#[test]
+fn markdown_table_rows_keep_their_columns() {
+	let pages = render_markdown(build_cli());
+	for name in ["eagle-create.md", "eagle-minecraft-create.md"] {
+		let rows: Vec<&str> = page(&pages, name)
+			.lines()
+			.filter(|l| l.starts_with('|'))
+			.collect();
+		assert!(!rows.is_empty(), "{name}");
+		for row in rows {
+			let columns = row.replace("\\|", "").matches('|').count() - 1;
+			assert_eq!(columns, 3, "{name}: {row}");
+		}
+	}
+	let create = page(&pages, "eagle-create.md");
+	assert!(create.contains("discord \\| next \\| typescript"));
+}
+
+#[test]
+fn markdown_lists_aliases() {
+	let pages = render_markdown(build_cli());
+	assert!(page(&pages, "eagle-create.md").contains("Aliases: `c`"));
+	assert!(page(&pages, "eagle-minecraft.md").contains("Aliases: `m`"));
+	assert!(page(&pages, "eagle-eaglecord.md").contains("Aliases: `e`"));
+}
+
+#[test]
+fn markdown_skips_hidden_commands() {
+	let pages = render_markdown(build_cli());
+	assert!(!pages.iter().any(|(file, _)| file.contains("eaglecord-dev")));
+	assert!(!pages.iter().any(|(file, _)| file == "eagle-docs.md"));
+}
+
+#[test]
+fn man_pages_include_alias_section() {
+	let pages = render_man(build_cli()).unwrap();
+	assert!(page(&pages, "eagle-update.1").contains("ALIASES"));
+	assert!(page(&pages, "eagle.1").contains(".TH"));
+}
+
+#[test]
+fn docs_command_writes_files() {
+	let tmp = TempDir::new().unwrap();
+	common::eagle()
+		.args(["docs", "--format", "man", "--out"])
+		.arg(tmp.path())
+		.assert()
+		.success();
+
+	assert!(tmp.path().join("eagle-minecraft-create.1").exists());
+}
@@ -0,0 +1,60 @@
+#![allow(clippy::indexing_slicing, clippy::unwrap_used)]
+
+mod common;
+
+use common::Response;
+use eagle::commands::doctor::parse_java_major;
+use tempfile::TempDir;
+
+fn doctor_json(tmp: &TempDir, ok: &str, broken: &str) -> serde_json::Value {
+	let output = common::eagle()
+		.env("EAGLE_CONFIG", tmp.path().join("config.toml"))
+		.env("EAGLE_CREATE_ROOT", tmp.path().join("projects"))
+		.env("EAGLE_MINECRAFT_SERVERS_ROOT", tmp.path())
+		.env("EAGLE_GITHUB_API", ok)
+		.env("EAGLE_PAPER_API", ok)
+		.env("EAGLE_FABRIC_META", broken)
+		.args(["--json", "doctor"])
+		.output()
+		.unwrap();
+	serde_json::from_slice(&output.stdout).unwrap()
+}
+
+fn status_of<'a>(doc: &'a serde_json::Value, name: &str) -> &'a str {
+	doc.pointer("/result/checks")
+		.and_then(|v| v.as_array())
+		.unwrap()
+		.iter()
+		.find(|c| c["name"] == name)
+		.and_then(|c| c["status"].as_str())
+		.unwrap()
+}
+
+#[test]
+fn parses_java_versions() {
+	let modern = "openjdk version \"21.0.2\" 2024-01-16\nOpenJDK Runtime";
+	assert_eq!(parse_java_major(modern), Some(21));
+	assert_eq!(parse_java_major("java version \"1.8.0_392\""), Some(8));
+	assert_eq!(
+		parse_java_major("openjdk version \"17\" 2021-09-14"),
+		Some(17)
+	);
+	assert_eq!(parse_java_major("no version here"), None);
+}
+
+#[test]
+fn reports_checks_against_stand_in_endpoints() {
+	let tmp = TempDir::new().unwrap();
+	let ok = common::stand_in(Response::ok("{}"));
+	let broken = common::stand_in(
+		Response::ok("{}").status("500 Internal Server Error"),
+	);
+
+	let doc = doctor_json(&tmp, &ok.url, &broken.url);
+	assert_eq!(doc["ok"], false);
+	assert_eq!(status_of(&doc, "github api"), "pass");
+	assert_eq!(status_of(&doc, "paper api"), "pass");
+	assert_eq!(status_of(&doc, "fabric meta"), "fail");
+	assert_eq!(status_of(&doc, "create root"), "pass");
+	assert_eq!(status_of(&doc, "servers root"), "pass");
+}
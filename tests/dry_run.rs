@@ -0,0 +1,86 @@
+#![allow(clippy::unwrap_used)]
+
+mod common;
+
+use std::sync::Arc;
+
+use eagle::exec::{DryRun, Executor};
+use eagle::process::Scripted;
+use predicates::str::contains;
+use tempfile::TempDir;
+
+#[test]
+fn config_set_writes_nothing() {
+	let tmp = TempDir::new().unwrap();
+	let config = tmp.path().join("config.toml");
+
+	common::eagle()
+		.env("EAGLE_CONFIG", &config)
+		.args(["--dry-run", "config", "set", "minecraft.port", "25570"])
+		.assert()
+		.success()
+		.stdout(contains("dry-run: would write"));
+
+	assert!(!config.exists());
+}
+
+#[test]
+fn minecraft_create_keeps_existing_folder() {
+	let tmp = TempDir::new().unwrap();
+	let existing = tmp.path().join("lobby");
+	std::fs::create_dir_all(&existing).unwrap();
+	std::fs::write(existing.join("world.dat"), "keep").unwrap();
+
+	common::eagle()
+		.env("EAGLE_CONFIG", tmp.path().join("config.toml"))
+		.env("EAGLE_MINECRAFT_SERVERS_ROOT", tmp.path())
+		.args([
+			"--dry-run",
+			"minecraft",
+			"create",
+			"--name",
+			"lobby",
+			"--type",
+			"paper",
+			"--version",
+			"1.21.11",
+			"--skip-download",
+			"--force",
+		])
+		.assert()
+		.success()
+		.stdout(contains("dry-run: would delete"))
+		.stdout(contains("eula.txt"));
+
+	assert!(existing.join("world.dat").exists());
+	assert!(!existing.join("eula.txt").exists());
+}
+
+#[test]
+fn quiet_still_prints_the_plan() {
+	let tmp = TempDir::new().unwrap();
+
+	common::eagle()
+		.env("EAGLE_CONFIG", tmp.path().join("config.toml"))
+		.args([
+			"--dry-run",
+			"-q",
+			"config",
+			"set",
+			"minecraft.port",
+			"25570",
+		])
+		.assert()
+		.success()
+		.stdout(contains("dry-run: would write"));
+}
+
+#[test]
+fn captures_still_run() {
+	let runner = Arc::new(Scripted::new().reply("git status", 0, "M x"));
+	let exec = DryRun::new(runner.clone());
+
+	assert_eq!(exec.run_capture("git", &["status"]).unwrap(), "M x");
+	exec.run_inherit("git", &["pull"], None).unwrap();
+	assert_eq!(runner.lines(), ["git status"]);
+}
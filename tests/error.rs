@@ -0,0 +1,71 @@
+#![allow(clippy::unwrap_used)]
+
+mod common;
+
+use eagle::error::{self, Category};
+use predicates::str::contains;
+use tempfile::TempDir;
+
+#[test]
+fn categories_have_distinct_exit_codes() {
+	let all = [
+		Category::General,
+		Category::Usage,
+		Category::MissingTool,
+		Category::Network,
+		Category::Integrity,
+		Category::Io,
+		Category::Cancelled,
+		Category::ExternalProcess,
+	];
+	let mut codes: Vec<i32> = all.iter().map(|c| c.exit_code()).collect();
+	codes.sort_unstable();
+	codes.dedup();
+	assert_eq!(codes.len(), all.len());
+}
+
+#[test]
+fn category_is_found_through_context() {
+	let err = error::integrity("sha256 mismatch").context("Downloading jar");
+	assert_eq!(error::category(&err), Category::Integrity);
+
+	let io = std::io::Error::new(std::io::ErrorKind::Interrupted, "read");
+	let err = anyhow::Error::new(dialoguer::Error::IO(io)).context("prompt");
+	assert_eq!(error::category(&err), Category::Cancelled);
+
+	let err = anyhow::anyhow!("plain");
+	assert_eq!(error::category(&err), Category::General);
+	assert!(error::hint(&err).is_none());
+}
+
+#[test]
+fn attached_hint_wins_over_category_default() {
+	let err = error::missing_tool("bun", "Install Bun: https://bun.sh");
+	assert_eq!(error::category(&err), Category::MissingTool);
+	assert_eq!(
+		error::hint(&err).as_deref(),
+		Some("Install Bun: https://bun.sh")
+	);
+}
+
+#[test]
+fn usage_errors_exit_with_2() {
+	common::eagle()
+		.arg("definitely-not-a-command")
+		.assert()
+		.code(2);
+}
+
+#[test]
+fn json_error_reports_category_and_hint() {
+	let tmp = TempDir::new().unwrap();
+	common::eagle()
+		.env("EAGLE_CONFIG", tmp.path().join("config.toml"))
+		.env("EAGLE_MINECRAFT_SERVERS_ROOT", tmp.path())
+		.env("PATH", tmp.path())
+		.args(["--json", "minecraft", "start", "nope"])
+		.assert()
+		.code(3)
+		.stdout(contains("\"category\":\"missing-tool\""))
+		.stdout(contains("\"hint\":"));
+}
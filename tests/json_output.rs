@@ -0,0 +1,66 @@
+#![allow(clippy::indexing_slicing, clippy::unwrap_used)]
+
+mod common;
+
+use tempfile::TempDir;
+
+fn parse_stdout(output: &std::process::Output) -> serde_json::Value {
+	serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[test]
+fn version_json_reports_result() {
+	let output = common::eagle()
+		.args(["version", "--json"])
+		.output()
+		.unwrap();
+	assert!(output.status.success());
+
+	let doc = parse_stdout(&output);
+	assert_eq!(doc["ok"], true);
+	assert_eq!(doc["command"], "version");
+	assert!(
+		doc["result"]["version"]
+			.as_str()
+			.unwrap()
+			.starts_with(env!("CARGO_PKG_VERSION"))
+	);
+}
+
+#[test]
+fn json_flag_is_global() {
+	let output = common::eagle()
+		.args(["--json", "version"])
+		.output()
+		.unwrap();
+	assert_eq!(parse_stdout(&output)["ok"], true);
+}
+
+#[test]
+fn errors_are_reported_as_json() {
+	let tmp = TempDir::new().unwrap();
+	let output = common::eagle()
+		.env("EAGLE_CONFIG", tmp.path().join("config.toml"))
+		.args(["--json", "config", "get", "minecraft.port"])
+		.output()
+		.unwrap();
+	assert!(!output.status.success());
+
+	let doc = parse_stdout(&output);
+	assert_eq!(doc["ok"], false);
+	assert!(doc["error"].as_str().unwrap().contains("not set"));
+}
+
+#[test]
+fn messages_go_to_stderr_in_json_mode() {
+	let tmp = TempDir::new().unwrap();
+	let output = common::eagle()
+		.env("EAGLE_CONFIG", tmp.path().join("config.toml"))
+		.args(["--json", "config", "set", "minecraft.port", "25565"])
+		.output()
+		.unwrap();
+
+	let doc = parse_stdout(&output);
+	assert_eq!(doc["result"]["value"], 25565);
+	assert!(String::from_utf8_lossy(&output.stderr).contains("\"event\""));
+}
@@ -1,3 +1,5 @@
+#![allow(clippy::unwrap_used)]
+
 use eagle::commands::minecraft::fabric::{
 	InstallerPart, LoaderCombo, LoaderPart, parse_sha256_token, pick_best_combo,
 };
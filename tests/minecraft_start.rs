@@ -1,3 +1,5 @@
+#![allow(clippy::indexing_slicing, clippy::unwrap_used)]
+
 use std::path::Path;
 
 use eagle::commands::minecraft::start::build_java_args;
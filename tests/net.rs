@@ -1,8 +1,10 @@
+#![allow(clippy::unwrap_used)]
+
 use std::path::Path;
 
 use eagle::net::{
-	format_bytes, is_retryable_http_error, normalize_sha256, retry_delay,
-	temp_download_path,
+	format_bytes, is_retryable_http_error, next_link, normalize_sha256,
+	retry_delay, temp_download_path,
 };
 
 #[test]
@@ -105,3 +107,13 @@ fn temp_download_path_preserves_parent() {
 	let p = temp_download_path(Path::new("/some/dir/file.jar"));
 	assert_eq!(p.parent().unwrap(), Path::new("/some/dir"));
 }
+
+#[test]
+fn next_link_finds_the_next_page() {
+	let header = r#"<https://api.github.com/x?page=3>; rel="next", <https://api.github.com/x?page=5>; rel="last""#;
+	assert_eq!(next_link(header), Some("https://api.github.com/x?page=3"));
+
+	let last = r#"<https://api.github.com/x?page=4>; rel="prev""#;
+	assert_eq!(next_link(last), None);
+	assert_eq!(next_link(""), None);
+}
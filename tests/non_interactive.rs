@@ -0,0 +1,51 @@
+#![allow(clippy::unwrap_used)]
+
+mod common;
+
+use predicates::str::contains;
+use tempfile::TempDir;
+
+#[test]
+fn missing_input_names_the_flag_instead_of_defaulting() {
+	let tmp = TempDir::new().unwrap();
+
+	common::eagle()
+		.env("EAGLE_CONFIG", tmp.path().join("config.toml"))
+		.env("EAGLE_MINECRAFT_SERVERS_ROOT", tmp.path())
+		.args(["minecraft", "create", "--type", "paper", "--skip-download"])
+		.args(["--version", "1.21.11"])
+		.assert()
+		.code(2)
+		.stderr(contains("pass --name <NAME>"));
+
+	assert!(!tmp.path().join("mc-server").exists());
+}
+
+#[test]
+fn missing_server_type_is_an_error() {
+	let tmp = TempDir::new().unwrap();
+
+	common::eagle()
+		.env("EAGLE_CONFIG", tmp.path().join("config.toml"))
+		.env("EAGLE_MINECRAFT_SERVERS_ROOT", tmp.path())
+		.args([
+			"--non-interactive",
+			"minecraft",
+			"create",
+			"--name",
+			"lobby",
+		])
+		.args(["--version", "1.21.11", "--skip-download"])
+		.assert()
+		.code(2)
+		.stderr(contains("--type <paper|fabric>"));
+}
+
+#[test]
+fn uninstall_requires_yes() {
+	common::eagle()
+		.args(["uninstall", "--force"])
+		.assert()
+		.code(2)
+		.stderr(contains("pass --yes"));
+}
@@ -0,0 +1,134 @@
+#![allow(clippy::indexing_slicing, clippy::unwrap_used)]
+
+mod common;
+
+use std::time::Instant;
+
+use common::{Response, StandIn};
+use eagle::commands::update::Channel;
+use eagle::net::Endpoints;
+use eagle::notice::{self, Cache, INTERVAL};
+use predicates::str::contains;
+use tempfile::TempDir;
+
+/// Serves `tag` as the latest release.
+fn github(tag: &str) -> StandIn {
+	common::stand_in(Response::ok(format!(
+		r#"[{{"tag_name":"{tag}","assets":[]}}]"#
+	)))
+}
+
+fn endpoints(github_api: String) -> Endpoints {
+	Endpoints {
+		github_api,
+		paper_api: String::new(),
+		fabric_meta: String::new(),
+	}
+}
+
+#[test]
+fn checks_at_most_once_per_interval() {
+	let tmp = TempDir::new().unwrap();
+	let path = tmp.path().join(notice::CACHE_FILE);
+	let github = github("v9.0.0");
+	let endpoints = endpoints(github.url.clone());
+	let now = 1_700_000_000;
+
+	let latest =
+		notice::latest_version(&endpoints, Channel::Stable, &path, now);
+	assert_eq!(latest.as_deref(), Some("9.0.0"));
+	assert_eq!(
+		notice::read_cache(&path),
+		Some(Cache {
+			checked_at: now,
+			latest_version: Some("9.0.0".to_string()),
+		})
+	);
+
+	let later = now + INTERVAL.as_secs() - 1;
+	notice::latest_version(&endpoints, Channel::Stable, &path, later);
+	assert_eq!(github.requests(), 1);
+
+	let next_day = now + INTERVAL.as_secs();
+	notice::latest_version(&endpoints, Channel::Stable, &path, next_day);
+	assert_eq!(github.requests(), 2);
+}
+
+#[test]
+fn failed_checks_are_cached_too() {
+	let tmp = TempDir::new().unwrap();
+	let path = tmp.path().join(notice::CACHE_FILE);
+	let endpoints = endpoints("http://127.0.0.1:1".to_string());
+
+	assert_eq!(
+		notice::latest_version(&endpoints, Channel::Stable, &path, 100),
+		None
+	);
+	assert_eq!(notice::read_cache(&path).unwrap().checked_at, 100);
+}
+
+#[test]
+fn message_only_for_newer_releases() {
+	assert!(
+		notice::message("3.3.13", "3.4.0")
+			.unwrap()
+			.contains("v3.4.0")
+	);
+	assert_eq!(notice::message("3.3.13", "3.3.13"), None);
+	assert_eq!(notice::message("3.4.0", "3.3.13"), None);
+}
+
+#[test]
+fn version_check_reports_latest_and_refreshes_the_cache() {
+	let tmp = TempDir::new().unwrap();
+	let github = github("v999.0.0");
+
+	let output = common::eagle()
+		.env("EAGLE_HOME", tmp.path())
+		.env("EAGLE_GITHUB_API", &github.url)
+		.args(["--json", "version", "--check"])
+		.output()
+		.unwrap();
+	assert!(output.status.success());
+
+	let doc: serde_json::Value =
+		serde_json::from_slice(&output.stdout).unwrap();
+	assert_eq!(doc["result"]["latest_version"], "999.0.0");
+	assert_eq!(doc["result"]["update_available"], true);
+
+	let cache = notice::read_cache(&tmp.path().join(notice::CACHE_FILE));
+	assert_eq!(cache.unwrap().latest_version.as_deref(), Some("999.0.0"));
+}
+
+#[test]
+fn notice_follows_a_command_when_turned_on() {
+	let github = github("v999.0.0");
+	common::eagle()
+		.env("EAGLE_GITHUB_API", &github.url)
+		.env("EAGLE_UPDATE_NOTIFY", "true")
+		.args(["config", "path"])
+		.assert()
+		.success()
+		.stdout(contains("eagle v999.0.0 is available"));
+}
+
+#[test]
+fn offline_check_is_silent_and_gives_up_quickly() {
+	let stalling = common::stand_in(Response::ok("").stall());
+
+	for api in ["http://127.0.0.1:1".to_string(), stalling.url.clone()] {
+		let started = Instant::now();
+		let output = common::eagle()
+			.env("EAGLE_GITHUB_API", &api)
+			.env("EAGLE_UPDATE_NOTIFY", "true")
+			.args(["config", "path"])
+			.output()
+			.unwrap();
+		assert!(output.status.success());
+		assert!(started.elapsed() < notice::CHECK_TIMEOUT * 3);
+		let stderr = String::from_utf8_lossy(&output.stderr);
+		let stdout = String::from_utf8_lossy(&output.stdout);
+		assert!(!stderr.contains("Retrying"), "{stderr}");
+		assert_eq!(stdout.lines().count(), 1, "{stdout}");
+	}
+}
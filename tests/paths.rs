@@ -0,0 +1,79 @@
+#![allow(clippy::unwrap_used)]
+
+mod common;
+
+use std::path::PathBuf;
+
+use eagle::paths::Paths;
+use tempfile::TempDir;
+
+#[test]
+fn home_roots_every_default() {
+	let paths = Paths::new(Some(PathBuf::from("/mnt/eagle")));
+
+	assert_eq!(
+		paths.config_file().unwrap(),
+		PathBuf::from("/mnt/eagle/config.toml")
+	);
+	assert_eq!(paths.data_dir().unwrap(), PathBuf::from("/mnt/eagle"));
+	assert_eq!(
+		paths.eaglecord_dir().unwrap(),
+		PathBuf::from("/mnt/eagle/eaglecord")
+	);
+	assert_eq!(
+		paths.servers_root().unwrap(),
+		PathBuf::from("/mnt/eagle/mc-servers")
+	);
+	assert_eq!(
+		paths.projects_root("26").unwrap(),
+		PathBuf::from("/mnt/eagle/projects/.26")
+	);
+}
+
+#[test]
+fn eagle_home_sandboxes_config_log_and_servers() {
+	let tmp = TempDir::new().unwrap();
+	let eagle = || {
+		let mut cmd = common::eagle();
+		cmd.env("EAGLE_HOME", tmp.path())
+			.env_remove("EAGLE_CONFIG")
+			.env_remove("EAGLE_RUN_LOG")
+			.env_remove("EAGLE_MINECRAFT_SERVERS_ROOT");
+		cmd
+	};
+
+	eagle()
+		.args(["config", "set", "minecraft.port", "25570"])
+		.assert()
+		.success();
+	eagle()
+		.args(["minecraft", "create", "--name", "lobby", "--type", "paper"])
+		.args(["--version", "1.21.11", "--skip-download"])
+		.assert()
+		.success();
+
+	assert!(tmp.path().join("config.toml").exists());
+	assert!(tmp.path().join("runs.jsonl").exists());
+	let properties = std::fs::read_to_string(
+		tmp.path().join("mc-servers/lobby/server.properties"),
+	)
+	.unwrap();
+	assert!(properties.contains("server-port=25570"));
+}
+
+#[test]
+fn per_path_settings_still_win() {
+	let tmp = TempDir::new().unwrap();
+	let servers = tmp.path().join("elsewhere");
+
+	common::eagle()
+		.env("EAGLE_HOME", tmp.path())
+		.env("EAGLE_MINECRAFT_SERVERS_ROOT", &servers)
+		.args(["minecraft", "create", "--name", "lobby", "--type", "paper"])
+		.args(["--version", "1.21.11", "--skip-download"])
+		.assert()
+		.success();
+
+	assert!(servers.join("lobby").exists());
+	assert!(!tmp.path().join("mc-servers").exists());
+}
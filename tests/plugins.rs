@@ -0,0 +1,97 @@
+#![allow(clippy::indexing_slicing)]
+#![cfg(unix)]
+#![allow(clippy::unwrap_used)]
+
+mod common;
+
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+
+use eagle::plugins::discover_in;
+use predicates::str::contains;
+use tempfile::TempDir;
+
+fn write_script(dir: &Path, name: &str, body: &str) {
+	let path = dir.join(name);
+	std::fs::write(&path, format!("#!/bin/sh\n{body}\n")).unwrap();
+	std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))
+		.unwrap();
+}
+
+fn path_with(dir: &Path) -> std::ffi::OsString {
+	let rest = std::env::var_os("PATH").unwrap_or_default();
+	let dirs =
+		std::iter::once(dir.to_path_buf()).chain(std::env::split_paths(&rest));
+	std::env::join_paths(dirs).unwrap()
+}
+
+#[test]
+fn discovers_executables_first_dir_wins() {
+	let a = TempDir::new().unwrap();
+	let b = TempDir::new().unwrap();
+	write_script(a.path(), "eagle-hello", "true");
+	write_script(b.path(), "eagle-hello", "true");
+	write_script(b.path(), "eagle-zzz", "true");
+	std::fs::write(b.path().join("eagle-notexec"), "x").unwrap();
+	std::fs::write(b.path().join("other"), "x").unwrap();
+
+	let found = discover_in([a.path().to_path_buf(), b.path().to_path_buf()]);
+	let names: Vec<&str> = found.iter().map(|p| p.name.as_str()).collect();
+	assert_eq!(names, ["hello", "zzz"]);
+	assert!(found[0].path.starts_with(a.path()));
+}
+
+#[test]
+fn runs_plugin_with_args_and_context() {
+	let tmp = TempDir::new().unwrap();
+	write_script(
+		tmp.path(),
+		"eagle-hello",
+		"echo \"args=$* version=$EAGLE_VERSION config=$EAGLE_CONFIG\"\n\
+		 test -n \"$EAGLE_EXE\" && test -n \"$EAGLE_DEV_MODE\"",
+	);
+	let config = tmp.path().join("config.toml");
+
+	common::eagle()
+		.env("PATH", path_with(tmp.path()))
+		.env("EAGLE_CONFIG", &config)
+		.args(["hello", "a", "--flag"])
+		.assert()
+		.success()
+		.stdout(contains("args=a --flag"))
+		.stdout(contains(format!("version={}", env!("CARGO_PKG_VERSION"))))
+		.stdout(contains(format!("config={}", config.display())));
+}
+
+#[test]
+fn forwards_plugin_exit_code() {
+	let tmp = TempDir::new().unwrap();
+	write_script(tmp.path(), "eagle-fail", "exit 7");
+
+	common::eagle()
+		.env("PATH", path_with(tmp.path()))
+		.arg("fail")
+		.assert()
+		.code(7);
+}
+
+#[test]
+fn plugins_show_in_help_and_suggestions() {
+	let tmp = TempDir::new().unwrap();
+	write_script(tmp.path(), "eagle-deploy", "true");
+
+	common::eagle()
+		.env("PATH", path_with(tmp.path()))
+		.arg("help")
+		.assert()
+		.success()
+		.stdout(contains("Plugins:"))
+		.stdout(contains("deploy"));
+
+	common::eagle()
+		.env("PATH", path_with(tmp.path()))
+		.arg("deplyo")
+		.assert()
+		.failure()
+		.stderr(contains("Did you mean: deploy?"));
+}
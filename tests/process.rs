@@ -0,0 +1,286 @@
+#![allow(clippy::unwrap_used)]
+
+use std::path::Path;
+use std::sync::Arc;
+
+use eagle::commands::iter_specs;
+use eagle::config::Config;
+use eagle::context::Context;
+use eagle::error::{self, Category};
+use eagle::paths::Paths;
+use eagle::process::Scripted;
+use tempfile::TempDir;
+
+/// Parses `args` with the registered command and runs it against `runner`,
+/// with all eagle state under `home`.
+fn run(
+	runner: &Arc<Scripted>,
+	home: &Path,
+	args: &[&str],
+) -> anyhow::Result<()> {
+	let name = args.first().copied().unwrap();
+	let spec = iter_specs().into_iter().find(|s| s.name == name).unwrap();
+	let matches = (spec.command)().try_get_matches_from(args).unwrap();
+
+	let mut ctx = Context::new().unwrap().with_processes(runner.clone());
+	ctx.config = Config::default();
+	ctx.paths = Paths::new(Some(home.to_path_buf()));
+	spec.check(&matches, &ctx)?;
+	(spec.run)(&matches, &ctx)
+}
+
+#[test]
+fn create_clones_then_updates_with_bun() {
+	let tmp = TempDir::new().unwrap();
+	let runner = Arc::new(Scripted::new());
+	let root = tmp.path().to_string_lossy();
+
+	run(
+		&runner,
+		tmp.path(),
+		&[
+			"create",
+			"--name",
+			"demo",
+			"-t",
+			"typescript",
+			"--root",
+			&root,
+		],
+	)
+	.unwrap();
+
+	let project = tmp.path().join("typescript").join("demo");
+	assert_eq!(
+		runner.lines(),
+		[
+			format!(
+				"git clone https://github.com/meowlounge/typescript-template.git {}",
+				project.display()
+			),
+			"bun update --latest".to_string(),
+		]
+	);
+	let bun = runner.calls().pop().unwrap();
+	assert_eq!(bun.dir.as_deref(), Some(project.as_path()));
+}
+
+#[test]
+fn create_stops_when_clone_fails() {
+	let tmp = TempDir::new().unwrap();
+	let runner = Arc::new(Scripted::new().reply("git clone", 128, ""));
+	let root = tmp.path().to_string_lossy();
+
+	let err = run(
+		&runner,
+		tmp.path(),
+		&["create", "--name", "demo", "-t", "next", "--root", &root],
+	)
+	.unwrap_err();
+
+	assert_eq!(error::category(&err), Category::ExternalProcess);
+	assert_eq!(runner.calls().len(), 1);
+}
+
+#[test]
+fn create_requires_bun() {
+	let tmp = TempDir::new().unwrap();
+	let runner = Arc::new(Scripted::new().missing("bun"));
+	let root = tmp.path().to_string_lossy();
+
+	let err = run(
+		&runner,
+		tmp.path(),
+		&["create", "--name", "demo", "-t", "next", "--root", &root],
+	)
+	.unwrap_err();
+
+	assert_eq!(error::category(&err), Category::MissingTool);
+	assert!(runner.calls().is_empty());
+}
+
+#[test]
+fn create_suggests_a_template_for_a_typo() {
+	let tmp = TempDir::new().unwrap();
+	let runner = Arc::new(Scripted::new());
+	let root = tmp.path().to_string_lossy();
+
+	let err = run(
+		&runner,
+		tmp.path(),
+		&["create", "--name", "demo", "-t", "nxet", "--root", &root],
+	)
+	.unwrap_err();
+
+	assert_eq!(error::category(&err), Category::Usage);
+	assert_eq!(error::hint(&err).as_deref(), Some("Did you mean 'next'?"));
+	assert!(runner.calls().is_empty());
+}
+
+#[test]
+fn eaglecord_fresh_install_clones_builds_and_injects() {
+	let tmp = TempDir::new().unwrap();
+	let runner = Arc::new(Scripted::new());
+
+	run(&runner, tmp.path(), &["eaglecord"]).unwrap();
+
+	let clone_dir = tmp.path().join("eaglecord/Vencord");
+	assert_eq!(
+		runner.lines(),
+		[
+			format!(
+				"git clone https://github.com/prodbyeagle/cord {}",
+				clone_dir.display()
+			),
+			"bun install".to_string(),
+			"bun run build".to_string(),
+			"bun inject".to_string(),
+		]
+	);
+}
+
+#[test]
+fn eaglecord_updates_an_outdated_clone() {
+	let tmp = TempDir::new().unwrap();
+	let clone_dir = tmp.path().join("eaglecord/Vencord");
+	std::fs::create_dir_all(clone_dir.join("packages/discord-types")).unwrap();
+	let dir = clone_dir.display().to_string();
+	let runner = Arc::new(
+		Scripted::new()
+			.reply(&format!("git -C {dir} rev-parse HEAD"), 0, "aaa\n")
+			.reply("git ls-remote", 0, "bbb\tHEAD\n"),
+	);
+
+	run(&runner, tmp.path(), &["eaglecord"]).unwrap();
+
+	assert_eq!(
+		runner.lines(),
+		[
+			format!("git -C {dir} status --porcelain"),
+			format!("git -C {dir} rev-parse HEAD"),
+			"git ls-remote https://github.com/prodbyeagle/cord HEAD"
+				.to_string(),
+			format!("git -C {dir} fetch origin"),
+			format!("git -C {dir} pull --ff-only"),
+			"bun link".to_string(),
+			"bun install".to_string(),
+			"bun run build".to_string(),
+			"bun inject".to_string(),
+		]
+	);
+}
+
+#[test]
+fn eaglecord_refuses_a_dirty_clone() {
+	let tmp = TempDir::new().unwrap();
+	std::fs::create_dir_all(tmp.path().join("eaglecord/Vencord")).unwrap();
+	let runner = Arc::new(Scripted::new().reply(
+		"git -C",
+		0,
+		" M src/plugins/index.ts\n",
+	));
+
+	let err = run(&runner, tmp.path(), &["eaglecord"]).unwrap_err();
+
+	assert!(err.to_string().contains("--reinstall"));
+	assert_eq!(runner.calls().len(), 1);
+}
+
+#[test]
+fn eaglecord_build_failure_stops_before_inject() {
+	let tmp = TempDir::new().unwrap();
+	let runner = Arc::new(Scripted::new().reply("bun run build", 1, ""));
+
+	let err = run(&runner, tmp.path(), &["eaglecord"]).unwrap_err();
+
+	assert_eq!(error::category(&err), Category::ExternalProcess);
+	assert!(!runner.lines().contains(&"bun inject".to_string()));
+}
+
+#[test]
+fn minecraft_start_launches_java_in_the_server_folder() {
+	let tmp = TempDir::new().unwrap();
+	let server = tmp.path().join("mc-servers/lobby");
+	std::fs::create_dir_all(&server).unwrap();
+	std::fs::write(server.join("server.jar"), "").unwrap();
+	let runner = Arc::new(Scripted::new());
+
+	run(
+		&runner,
+		tmp.path(),
+		&["minecraft", "start", "lobby", "--ram-mb", "2048"],
+	)
+	.unwrap();
+
+	let calls = runner.calls();
+	assert_eq!(calls.len(), 1);
+	let java = calls.first().unwrap();
+	assert_eq!(java.program, "java");
+	assert_eq!(java.dir.as_deref(), Some(server.as_path()));
+	assert!(java.args.contains(&"-Xmx2048M".to_string()));
+	assert_eq!(java.args.last().map(String::as_str), Some("nogui"));
+}
+
+#[test]
+fn minecraft_start_takes_ram_from_the_parent_command() {
+	let tmp = TempDir::new().unwrap();
+	let server = tmp.path().join("mc-servers/lobby");
+	std::fs::create_dir_all(&server).unwrap();
+	std::fs::write(server.join("server.jar"), "").unwrap();
+	let runner = Arc::new(Scripted::new());
+
+	run(
+		&runner,
+		tmp.path(),
+		&["minecraft", "--ram-mb", "4096", "start", "lobby"],
+	)
+	.unwrap();
+
+	let calls = runner.calls();
+	let java = calls.first().unwrap();
+	assert!(java.args.contains(&"-Xmx4096M".to_string()));
+}
+
+#[test]
+fn minecraft_start_reports_a_crashed_server() {
+	let tmp = TempDir::new().unwrap();
+	let server = tmp.path().join("mc-servers/lobby");
+	std::fs::create_dir_all(&server).unwrap();
+	std::fs::write(server.join("server.jar"), "").unwrap();
+	let runner = Arc::new(Scripted::new().reply("java", 1, ""));
+
+	let err =
+		run(&runner, tmp.path(), &["minecraft", "start", "lobby"]).unwrap_err();
+
+	assert_eq!(error::category(&err), Category::ExternalProcess);
+}
+
+#[test]
+fn minecraft_start_requires_java() {
+	let tmp = TempDir::new().unwrap();
+	let runner = Arc::new(Scripted::new().missing("java"));
+
+	let err =
+		run(&runner, tmp.path(), &["minecraft", "start", "lobby"]).unwrap_err();
+
+	assert_eq!(error::category(&err), Category::MissingTool);
+	assert!(runner.calls().is_empty());
+}
+
+#[test]
+fn minecraft_start_suggests_a_server_for_a_typo() {
+	let tmp = TempDir::new().unwrap();
+	for name in ["lobby", "survival"] {
+		let server = tmp.path().join("mc-servers").join(name);
+		std::fs::create_dir_all(&server).unwrap();
+		std::fs::write(server.join("server.jar"), "").unwrap();
+	}
+	let runner = Arc::new(Scripted::new());
+
+	let err =
+		run(&runner, tmp.path(), &["minecraft", "start", "lobyy"]).unwrap_err();
+
+	assert_eq!(error::category(&err), Category::Usage);
+	assert_eq!(error::hint(&err).as_deref(), Some("Did you mean 'lobby'?"));
+	assert!(runner.calls().is_empty());
+}
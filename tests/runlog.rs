@@ -0,0 +1,159 @@
+#![allow(clippy::unwrap_used)]
+
+mod common;
+
+use std::io::BufRead;
+use std::path::Path;
+
+use eagle::runlog::{
+	HttpRecord, MAX_BYTES, Record, append, read_all, rotated_path,
+};
+use predicates::prelude::PredicateBooleanExt;
+use predicates::str::contains;
+use tempfile::TempDir;
+
+fn record(exit_code: i32) -> Record {
+	Record {
+		time: "2026-01-01T00:00:00Z".to_string(),
+		args: vec!["version".to_string()],
+		command: Some("version".to_string()),
+		exit_code,
+		..Record::default()
+	}
+}
+
+fn logs_json(log: &Path, args: &[&str]) -> Vec<serde_json::Value> {
+	let output = common::eagle()
+		.env("EAGLE_RUN_LOG", log)
+		.arg("--json")
+		.arg("logs")
+		.args(args)
+		.output()
+		.unwrap();
+	let doc: serde_json::Value =
+		serde_json::from_slice(&output.stdout).unwrap();
+	doc.pointer("/result/records")
+		.and_then(|v| v.as_array())
+		.cloned()
+		.unwrap()
+}
+
+#[test]
+fn rotates_and_reads_oldest_first() {
+	let tmp = TempDir::new().unwrap();
+	let path = tmp.path().join("runs.jsonl");
+
+	append(&path, &record(1)).unwrap();
+	let padding = "x".repeat(usize::try_from(MAX_BYTES).unwrap());
+	std::fs::OpenOptions::new()
+		.append(true)
+		.open(&path)
+		.and_then(|mut f| std::io::Write::write_all(&mut f, padding.as_bytes()))
+		.unwrap();
+	append(&path, &record(2)).unwrap();
+
+	assert!(rotated_path(&path, 1).exists());
+	let codes: Vec<i32> = read_all(&path)
+		.unwrap()
+		.iter()
+		.map(|r| r.exit_code)
+		.collect();
+	assert_eq!(codes, [1, 2]);
+}
+
+#[test]
+fn runs_are_logged_and_filtered() {
+	let tmp = TempDir::new().unwrap();
+	let log = tmp.path().join("runs.jsonl");
+
+	common::eagle()
+		.env("EAGLE_RUN_LOG", &log)
+		.arg("version")
+		.assert()
+		.success();
+	common::eagle()
+		.env("EAGLE_RUN_LOG", &log)
+		.args(["config", "get", "minecraft.port"])
+		.env("EAGLE_CONFIG", tmp.path().join("config.toml"))
+		.assert()
+		.failure();
+
+	let all = logs_json(&log, &[]);
+	assert_eq!(all.len(), 2);
+
+	let failed = logs_json(&log, &["--failed"]);
+	assert_eq!(failed.len(), 1);
+	let failed = failed.first().unwrap();
+	assert_eq!(failed["command"], "config");
+	assert!(
+		failed["errors"]
+			.to_string()
+			.contains("'minecraft.port' is not set")
+	);
+
+	let version = logs_json(&log, &["--command", "version", "--last", "5"]);
+	assert_eq!(version.len(), 1);
+}
+
+#[test]
+fn logs_details_show_requests_without_debug_output() {
+	let tmp = TempDir::new().unwrap();
+	let log = tmp.path().join("runs.jsonl");
+	let mut run = record(0);
+	run.http.push(HttpRecord {
+		method: "GET".to_string(),
+		url: "https://api.github.com/x".to_string(),
+		status: Some(200),
+		error: None,
+		duration_ms: 12,
+	});
+	append(&log, &run).unwrap();
+
+	common::eagle()
+		.env("EAGLE_RUN_LOG", &log)
+		.env("EAGLE_LOG", "info")
+		.args(["logs", "--details"])
+		.assert()
+		.success()
+		.stdout(contains("http: GET https://api.github.com/x → 200"))
+		.stderr(contains("[dbg]").not());
+
+	common::eagle()
+		.env("EAGLE_RUN_LOG", &log)
+		.arg("logs")
+		.assert()
+		.success()
+		.stdout(contains("http:").not());
+}
+
+#[cfg(unix)]
+#[test]
+fn follow_ends_cleanly_on_ctrl_c() {
+	let tmp = TempDir::new().unwrap();
+	let log = tmp.path().join("runs.jsonl");
+	let mut child = std::process::Command::new(env!("CARGO_BIN_EXE_eagle"))
+		.env("EAGLE_HOME", tmp.path())
+		.env("EAGLE_RUN_LOG", &log)
+		.args(["logs", "--follow", "--last", "0"])
+		.stdout(std::process::Stdio::piped())
+		.stderr(std::process::Stdio::null())
+		.spawn()
+		.unwrap();
+
+	// Once a new run shows up, follow is polling.
+	std::thread::sleep(std::time::Duration::from_millis(200));
+	append(&log, &record(0)).unwrap();
+	let mut line = String::new();
+	std::io::BufReader::new(child.stdout.take().unwrap())
+		.read_line(&mut line)
+		.unwrap();
+	assert!(line.contains("eagle version"), "{line}");
+
+	std::process::Command::new("kill")
+		.args(["-INT", &child.id().to_string()])
+		.status()
+		.unwrap();
+	let status = child.wait().unwrap();
+
+	assert_eq!(status.code(), Some(0));
+}
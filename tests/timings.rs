@@ -0,0 +1,75 @@
+#![allow(clippy::indexing_slicing, clippy::unwrap_used)]
+
+mod common;
+
+use eagle::timings::{self, Kind};
+use predicates::str::contains;
+use tempfile::TempDir;
+
+// The recorder is process-wide, so every in-process check lives in this one
+// test.
+#[test]
+fn spans_nest_and_render() {
+	drop(timings::span(Kind::Phase, "before enable"));
+	timings::enable();
+
+	{
+		let _command = timings::span(Kind::Command, "minecraft");
+		timings::phase("resolve Paper version", || {
+			let _http = timings::span(Kind::Http, "GET /v3/projects/paper");
+		});
+		timings::phase("download server jar", || ());
+	}
+
+	let spans = timings::finish();
+	let names: Vec<(&str, usize)> =
+		spans.iter().map(|s| (s.name.as_str(), s.depth)).collect();
+	assert_eq!(
+		names,
+		[
+			("minecraft", 0),
+			("resolve Paper version", 1),
+			("GET /v3/projects/paper", 2),
+			("download server jar", 1),
+		]
+	);
+	assert!(timings::finish().is_empty());
+
+	let table = timings::table(&spans);
+	assert!(table.contains("100%  command  minecraft"));
+	assert!(table.contains("phase      resolve Paper version"));
+	assert!(table.contains("http         GET /v3/projects/paper"));
+
+	let trace = timings::chrome_trace(&spans);
+	let events = trace["traceEvents"].as_array().unwrap();
+	assert_eq!(events.len(), 4);
+	assert_eq!(events[0]["ph"], "X");
+	assert_eq!(events[2]["cat"], "http");
+}
+
+#[test]
+fn timings_flag_prints_a_table() {
+	common::eagle()
+		.args(["--timings", "config", "list"])
+		.assert()
+		.success()
+		.stderr(contains("command  config"));
+}
+
+#[test]
+fn timings_file_gets_a_chrome_trace() {
+	let tmp = TempDir::new().unwrap();
+	let trace = tmp.path().join("trace.json");
+	common::eagle()
+		.env("EAGLE_HOME", tmp.path())
+		.arg(format!("--timings={}", trace.display()))
+		.args(["config", "list"])
+		.assert()
+		.success();
+
+	let doc: serde_json::Value =
+		serde_json::from_slice(&std::fs::read(&trace).unwrap()).unwrap();
+	let event = &doc["traceEvents"][0];
+	assert_eq!(event["name"], "config");
+	assert_eq!(event["cat"], "command");
+}
@@ -0,0 +1,65 @@
+use eagle::ui::{ColorChoice, Level, resolve_color_choice, resolve_level};
+
+#[test]
+fn level_parse_accepts_known_names() {
+	assert_eq!(Level::parse("trace"), Some(Level::Trace));
+	assert_eq!(Level::parse(" DEBUG "), Some(Level::Debug));
+	assert_eq!(Level::parse("warning"), Some(Level::Warn));
+	assert_eq!(Level::parse("loud"), None);
+}
+
+#[test]
+fn quiet_wins_over_everything() {
+	assert_eq!(resolve_level(true, 0, Some("trace"), true), Level::Error);
+}
+
+#[test]
+fn verbose_count_maps_to_debug_and_trace() {
+	assert_eq!(resolve_level(false, 1, None, false), Level::Debug);
+	assert_eq!(resolve_level(false, 2, None, false), Level::Trace);
+	assert_eq!(resolve_level(false, 5, Some("error"), false), Level::Trace);
+}
+
+#[test]
+fn env_overrides_build_default() {
+	assert_eq!(resolve_level(false, 0, Some("warn"), true), Level::Warn);
+	assert_eq!(resolve_level(false, 0, Some("bogus"), false), Level::Info);
+}
+
+#[test]
+fn dev_builds_default_to_debug() {
+	assert_eq!(resolve_level(false, 0, None, true), Level::Debug);
+	assert_eq!(resolve_level(false, 0, None, false), Level::Info);
+}
+
+#[test]
+fn explicit_color_flag_beats_env() {
+	assert_eq!(
+		resolve_color_choice(Some(ColorChoice::Always), Some("1"), None),
+		ColorChoice::Always
+	);
+	assert_eq!(
+		resolve_color_choice(Some(ColorChoice::Never), None, Some("1")),
+		ColorChoice::Never
+	);
+}
+
+#[test]
+fn no_color_beats_clicolor_force() {
+	assert_eq!(
+		resolve_color_choice(Some(ColorChoice::Auto), Some("1"), Some("1")),
+		ColorChoice::Never
+	);
+}
+
+#[test]
+fn empty_or_zero_env_values_are_ignored() {
+	assert_eq!(
+		resolve_color_choice(None, Some(""), Some("0")),
+		ColorChoice::Auto
+	);
+	assert_eq!(
+		resolve_color_choice(None, None, Some("1")),
+		ColorChoice::Always
+	);
+}
@@ -1,6 +1,104 @@
+#![allow(clippy::indexing_slicing, clippy::unwrap_used)]
+
+mod common;
+
 use std::path::Path;
 
-use eagle::commands::update::is_dev_exe;
+use common::{Response, StandIn};
+use eagle::commands::update::notes::{self, ReleaseNotes};
+use eagle::commands::update::{is_dev_exe, is_newer};
+
+/// Serves a release list with `tags` (a `-` in the tag marks a prerelease),
+/// each with a binary for every platform and a one-item changelog.
+fn github(tags: &[&str]) -> String {
+	let github = StandIn::new();
+	let assets: Vec<String> = [
+		"eagle",
+		"eagle.exe",
+		"eagle-x86_64-apple-darwin",
+		"eagle-aarch64-apple-darwin",
+	]
+	.iter()
+	.map(|name| {
+		format!(
+			r#"{{"name":"{name}","browser_download_url":"{}/{name}","digest":"sha256:{}"}}"#,
+			github.url,
+			"0".repeat(64)
+		)
+	})
+	.collect();
+	let releases: Vec<String> = tags
+		.iter()
+		.map(|tag| {
+			format!(
+				r###"{{"tag_name":"{tag}","name":"{tag}","prerelease":{},"published_at":"2026-10-01T12:00:00Z","body":"## Changes\r\n- Shipped {tag}","assets":[{}]}}"###,
+				tag.contains('-'),
+				assets.join(",")
+			)
+		})
+		.collect();
+	let body = format!("[{}]", releases.join(","));
+
+	github.serve(move |_| Response::ok(body.clone())).url
+}
+
+/// Serves the release lists `pages`, linking each to the next like GitHub.
+fn paged_github(pages: &[&[&str]]) -> String {
+	let github = StandIn::new();
+	let base = github.url.clone();
+	let bodies: Vec<String> = pages
+		.iter()
+		.map(|tags| {
+			let releases: Vec<String> = tags
+				.iter()
+				.map(|tag| format!(r#"{{"tag_name":"{tag}","assets":[]}}"#))
+				.collect();
+			format!("[{}]", releases.join(","))
+		})
+		.collect();
+
+	github
+		.serve(move |path| {
+			let page = path
+				.split(['?', '&'])
+				.find_map(|p| p.strip_prefix("page=")?.parse::<usize>().ok())
+				.unwrap_or(1);
+			let response = Response::ok(bodies[page - 1].clone());
+			if page < bodies.len() {
+				response.next_page(format!("{base}/releases?page={}", page + 1))
+			} else {
+				response
+			}
+		})
+		.url
+}
+
+/// Runs `eagle --json update <args>` against `api`.
+fn update(api: &str, env: &[(&str, &str)], args: &[&str]) -> serde_json::Value {
+	eagle(api, env, &[&["update"], args].concat())
+}
+
+fn eagle(api: &str, env: &[(&str, &str)], args: &[&str]) -> serde_json::Value {
+	let output = common::eagle()
+		.env("EAGLE_GITHUB_API", api)
+		.envs(env.iter().copied())
+		.arg("--json")
+		.args(args)
+		.output()
+		.unwrap();
+	serde_json::from_slice(&output.stdout).unwrap()
+}
+
+fn note_versions(notes: &serde_json::Value) -> Vec<&str> {
+	notes
+		.as_array()
+		.unwrap()
+		.iter()
+		.map(|n| n["version"].as_str().unwrap())
+		.collect()
+}
+
+const TAGS: &[&str] = &["v1.0.0", "v1000.0.0-beta.1", "v999.0.0"];
 
 #[test]
 fn dev_debug_path_detected() {
@@ -32,3 +130,207 @@ fn path_is_case_insensitive() {
 fn empty_path_not_dev() {
 	assert!(!is_dev_exe(Path::new("")));
 }
+
+#[test]
+fn newer_versions_compare_numerically() {
+	assert!(is_newer("3.10.0", "3.9.2"));
+	assert!(is_newer("v4.0.0", "3.3.13"));
+	assert!(is_newer("3.3.13", "3.3.13-beta.1"));
+	assert!(!is_newer("3.3.13-beta.1", "3.3.13"));
+	assert!(!is_newer("nightly", "3.3.13"));
+	assert!(!is_newer("3.3.12", "3.3.13"));
+}
+
+#[test]
+fn list_marks_the_latest_release_on_each_channel() {
+	let api = github(TAGS);
+
+	let stable = update(&api, &[], &["--list"]);
+	let releases = stable["result"]["releases"].as_array().unwrap();
+	let versions: Vec<&str> = releases
+		.iter()
+		.map(|r| r["version"].as_str().unwrap())
+		.collect();
+	assert_eq!(versions, ["1000.0.0-beta.1", "999.0.0", "1.0.0"]);
+	assert_eq!(releases[1]["latest"], true);
+	assert_eq!(releases[0]["prerelease"], true);
+
+	let pre =
+		update(&api, &[("EAGLE_UPDATE_CHANNEL", "prerelease")], &["--list"]);
+	assert_eq!(pre["result"]["channel"], "prerelease");
+	assert_eq!(pre["result"]["releases"][0]["latest"], true);
+}
+
+#[test]
+fn to_an_older_release_needs_allow_downgrade() {
+	let api = github(TAGS);
+
+	let refused = update(&api, &[], &["--force", "--to", "1.0.0"]);
+	assert_eq!(refused["category"], "usage");
+	assert!(
+		refused["hint"]
+			.as_str()
+			.unwrap()
+			.contains("--allow-downgrade")
+	);
+
+	let allowed = update(
+		&api,
+		&[],
+		&[
+			"--force",
+			"--dry-run",
+			"--to",
+			"v1.0.0",
+			"--allow-downgrade",
+		],
+	);
+	assert_eq!(allowed["ok"], true);
+	assert_eq!(allowed["result"]["new_version"], "1.0.0");
+}
+
+#[test]
+fn to_an_unknown_release_suggests_a_published_one() {
+	let doc = update(&github(TAGS), &[], &["--force", "--to", "2.0.0"]);
+	assert_eq!(doc["error"], "No release v2.0.0 found");
+	assert_eq!(doc["hint"], "Did you mean '1.0.0'?");
+}
+
+#[test]
+fn never_downgrades_to_an_older_latest_release() {
+	let doc = update(&github(&["v1.0.0"]), &[], &["--force"]);
+	assert_eq!(doc["result"]["updated"], false);
+	assert_eq!(doc["result"]["latest_version"], "1.0.0");
+}
+
+#[test]
+fn update_reports_the_notes_of_the_releases_it_skips_ahead() {
+	let api = github(&["v1.0.0", "v998.0.0", "v999.0.0-rc.1", "v999.0.0"]);
+
+	let doc = update(&api, &[], &["--force", "--dry-run"]);
+	assert_eq!(doc["result"]["new_version"], "999.0.0");
+	let notes = &doc["result"]["release_notes"];
+	assert_eq!(note_versions(notes), ["999.0.0", "998.0.0"]);
+	assert_eq!(notes[0]["body"], "## Changes\r\n- Shipped v999.0.0");
+	assert_eq!(notes[0]["date"], "2026-10-01");
+	assert_eq!(notes[0]["title"], serde_json::Value::Null);
+}
+
+#[test]
+fn changelog_starts_after_since_and_follows_the_channel() {
+	let api = github(TAGS);
+
+	let stable = eagle(&api, &[], &["changelog", "--since", "v0.1.0"]);
+	assert_eq!(stable["result"]["since"], "0.1.0");
+	assert_eq!(
+		note_versions(&stable["result"]["releases"]),
+		["999.0.0", "1.0.0"]
+	);
+
+	let pre = eagle(
+		&api,
+		&[("EAGLE_UPDATE_CHANNEL", "prerelease")],
+		&["changelog", "--since", "1.0.0"],
+	);
+	assert_eq!(
+		note_versions(&pre["result"]["releases"]),
+		["1000.0.0-beta.1", "999.0.0"]
+	);
+
+	let current = eagle(&github(&["v1.0.0"]), &[], &["changelog"]);
+	assert_eq!(current["ok"], true);
+	assert!(note_versions(&current["result"]["releases"]).is_empty());
+}
+
+#[test]
+fn changelog_notes_are_hidden_by_quiet() {
+	let api = github(TAGS);
+	let changelog = |quiet: &[&str]| {
+		let output = common::eagle()
+			.env("EAGLE_GITHUB_API", &api)
+			.args(quiet)
+			.args(["changelog", "--since", "0.1.0"])
+			.output()
+			.unwrap();
+		assert!(output.status.success());
+		String::from_utf8(output.stdout).unwrap()
+	};
+
+	assert!(changelog(&[]).contains("• Shipped v999.0.0"));
+	assert_eq!(changelog(&["-q"]), "");
+}
+
+#[test]
+fn release_lists_are_hidden_by_quiet() {
+	let api = github(TAGS);
+	let stdout = |args: &[&str]| {
+		let output = common::eagle()
+			.env("EAGLE_GITHUB_API", &api)
+			.args(args)
+			.output()
+			.unwrap();
+		assert!(output.status.success());
+		String::from_utf8(output.stdout).unwrap()
+	};
+
+	assert!(stdout(&["update", "--list"]).contains("v999.0.0"));
+	assert_eq!(stdout(&["-q", "update", "--list"]), "");
+	assert!(!stdout(&["update", "--list-installed"]).is_empty());
+	assert_eq!(stdout(&["-q", "update", "--list-installed"]), "");
+}
+
+#[test]
+fn list_reads_every_page_of_releases() {
+	let api = paged_github(&[&["v3.0.0", "v2.0.0"], &["v1.0.0"]]);
+	let doc = update(&api, &[], &["--list"]);
+	let versions: Vec<&str> = doc["result"]["releases"]
+		.as_array()
+		.unwrap()
+		.iter()
+		.map(|r| r["version"].as_str().unwrap())
+		.collect();
+	assert_eq!(versions, ["3.0.0", "2.0.0", "1.0.0"]);
+}
+
+#[test]
+fn confirm_needs_a_prompt_unless_yes_is_passed() {
+	let api = github(TAGS);
+
+	let refused = update(&api, &[], &["--force", "--dry-run", "--confirm"]);
+	assert_eq!(refused["category"], "usage");
+	assert!(refused["error"].as_str().unwrap().contains("--yes"));
+
+	let env = [("EAGLE_UPDATE_CONFIRM", "true")];
+	let configured = update(&api, &env, &["--force", "--dry-run"]);
+	assert_eq!(configured["category"], "usage");
+
+	let skipped = update(&api, &env, &["--force", "--dry-run", "--yes"]);
+	assert_eq!(skipped["result"]["updated"], true);
+}
+
+#[test]
+fn release_notes_render_headings_and_lists_plainly() {
+	let release =
+		|version: &str, title: Option<&str>, body: &str| ReleaseNotes {
+			version: semver::Version::parse(version).unwrap(),
+			title: title.map(str::to_string),
+			date: Some("2026-10-01".into()),
+			body: body.into(),
+		};
+	let rendered = notes::render(
+		&[
+			release(
+				"3.4.0",
+				Some("Faster servers"),
+				"## What's new\r\n\r\n\r\n* Paper 1.21\r\n  - nested\r\n\r\n",
+			),
+			release("3.3.13", None, ""),
+		],
+		false,
+	);
+	assert_eq!(
+		rendered,
+		"v3.4.0 – Faster servers (2026-10-01)\n  What's new\n\n  • Paper 1.21\n    • nested\n\n\
+v3.3.13 (2026-10-01)\n  No release notes.\n"
+	);
+}
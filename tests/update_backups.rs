@@ -0,0 +1,146 @@
+#![allow(clippy::unwrap_used)]
+
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use eagle::commands::iter_specs;
+use eagle::commands::update::backups::{self, Backup};
+use eagle::config::Config;
+use eagle::context::Context;
+use eagle::error::{self, Category};
+use eagle::exec::System;
+use eagle::paths::Paths;
+use eagle::process::Scripted;
+use tempfile::TempDir;
+
+/// Writes a backup of `version` saved `age_secs` ago.
+fn backup(dir: &Path, version: &str, age_secs: u64) {
+	std::fs::create_dir_all(dir).unwrap();
+	let path = dir.join(format!("eagle-{version}"));
+	std::fs::write(&path, version).unwrap();
+	let file = std::fs::File::options().write(true).open(&path).unwrap();
+	file.set_modified(SystemTime::now() - Duration::from_secs(age_secs))
+		.unwrap();
+}
+
+fn versions(backups: &[Backup]) -> Vec<&str> {
+	backups.iter().map(|b| b.version.as_str()).collect()
+}
+
+#[test]
+fn save_keeps_only_the_most_recent_backups() {
+	let tmp = TempDir::new().unwrap();
+	let dir = tmp.path().join(backups::DIR_NAME);
+	let exe = tmp.path().join("eagle");
+	std::fs::write(&exe, "3.0.0").unwrap();
+	backup(&dir, "1.0.0", 300);
+	backup(&dir, "2.0.0", 200);
+	backup(&dir, "2.5.0", 100);
+
+	let saved = backups::save(&System::default(), &exe, &dir, "3.0.0", 2)
+		.unwrap()
+		.unwrap();
+
+	assert_eq!(std::fs::read_to_string(saved).unwrap(), "3.0.0");
+	assert_eq!(versions(&backups::list(&dir).unwrap()), ["3.0.0", "2.5.0"]);
+}
+
+#[test]
+fn save_is_off_with_zero_keep() {
+	let tmp = TempDir::new().unwrap();
+	let dir = tmp.path().join(backups::DIR_NAME);
+	let exe = tmp.path().join("eagle");
+	std::fs::write(&exe, "").unwrap();
+
+	let saved =
+		backups::save(&System::default(), &exe, &dir, "3.0.0", 0).unwrap();
+	assert_eq!(saved, None);
+	assert!(!dir.exists());
+}
+
+#[test]
+fn pick_prefers_the_newest_older_backup() {
+	let tmp = TempDir::new().unwrap();
+	let dir = tmp.path().join(backups::DIR_NAME);
+	backup(&dir, "3.0.0", 10);
+	backup(&dir, "2.0.0", 20);
+	backup(&dir, "1.0.0", 30);
+	let installed = backups::list(&dir).unwrap();
+
+	let picked = backups::pick(&installed, "2.5.0", None).unwrap();
+	assert_eq!(picked.version, "2.0.0");
+
+	let picked = backups::pick(&installed, "2.5.0", Some("v1.0.0")).unwrap();
+	assert_eq!(picked.version, "1.0.0");
+
+	// Nothing older: roll forward to the most recent other version.
+	let picked = backups::pick(&installed, "0.9.0", None).unwrap();
+	assert_eq!(picked.version, "3.0.0");
+
+	let err = backups::pick(&installed, "2.5.0", Some("1.0.1")).unwrap_err();
+	assert_eq!(error::category(&err), Category::Usage);
+	assert_eq!(error::hint(&err).as_deref(), Some("Did you mean '1.0.0'?"));
+}
+
+#[test]
+fn rollback_restores_a_backup_without_saving_another() {
+	let tmp = TempDir::new().unwrap();
+	let dir = tmp.path().join(backups::DIR_NAME);
+	std::fs::write(tmp.path().join("eagle"), "2.0.0").unwrap();
+	backup(&dir, "1.0.0", 60);
+
+	let runner = Arc::new(Scripted::new());
+	let mut ctx = Context::new().unwrap().with_processes(runner.clone());
+	ctx.config = Config::default();
+	ctx.paths = Paths::new(Some(tmp.path().to_path_buf()));
+	ctx.exe_dir = tmp.path().to_path_buf();
+	ctx.exe_path = tmp.path().join("eagle");
+	ctx.version = "2.0.0";
+
+	let spec = iter_specs()
+		.into_iter()
+		.find(|s| s.name == "update")
+		.unwrap();
+	let matches = (spec.command)()
+		.try_get_matches_from(["update", "--rollback"])
+		.unwrap();
+	(spec.run)(&matches, &ctx).unwrap();
+
+	let restored = std::fs::read_to_string(tmp.path().join("eagle.new"));
+	assert_eq!(restored.unwrap(), "1.0.0");
+	assert!(!dir.join("eagle-2.0.0").exists());
+	let swap = runner.lines().pop().unwrap();
+	assert!(swap.starts_with("sh -c"));
+	assert!(swap.contains("mv -f"));
+}
+
+#[test]
+fn dev_install_saves_no_backup() {
+	let tmp = TempDir::new().unwrap();
+	let dir = tmp.path().join(backups::DIR_NAME);
+	std::fs::write(tmp.path().join("eagle"), "2.0.0").unwrap();
+	let dev_build = tmp.path().join("eagle-dev");
+	std::fs::write(&dev_build, "dev").unwrap();
+
+	let runner = Arc::new(Scripted::new());
+	let mut ctx = Context::new().unwrap().with_processes(runner);
+	ctx.config = Config::default();
+	ctx.paths = Paths::new(Some(tmp.path().to_path_buf()));
+	ctx.exe_dir = tmp.path().to_path_buf();
+	ctx.exe_path = tmp.path().join("eagle");
+	ctx.version = "2.0.0";
+
+	let spec = iter_specs()
+		.into_iter()
+		.find(|s| s.name == "update")
+		.unwrap();
+	let matches = (spec.command)()
+		.try_get_matches_from(["update", "--dev", &dev_build.to_string_lossy()])
+		.unwrap();
+	(spec.run)(&matches, &ctx).unwrap();
+
+	let installed = std::fs::read_to_string(tmp.path().join("eagle.new"));
+	assert_eq!(installed.unwrap(), "dev");
+	assert!(!dir.exists());
+}
@@ -1,4 +1,4 @@
-use eagle::util::escape_sh_single_quoted;
+use eagle::util::{closest, escape_sh_single_quoted};
 
 #[test]
 fn escape_no_special_chars() {
@@ -32,3 +32,21 @@ fn escape_path_with_apostrophe() {
 		r"/Users/user'\''s/eagle"
 	);
 }
+
+#[test]
+fn closest_suggests_a_near_typo() {
+	assert_eq!(closest("nxet", ["next", "vite", "svelte"]), Some("next"));
+	assert_eq!(closest("lobyy", ["lobby", "survival"]), Some("lobby"));
+}
+
+#[test]
+fn closest_prefers_case_insensitive_then_prefix_matches() {
+	assert_eq!(closest("Paper", ["papel", "paper"]), Some("paper"));
+	assert_eq!(closest("1.2", ["1.3", "1.21"]), Some("1.21"));
+}
+
+#[test]
+fn closest_ignores_far_candidates() {
+	assert_eq!(closest("minecraft", ["next", "vite"]), None);
+	assert_eq!(closest("x", std::iter::empty()), None);
+}
@@ -0,0 +1,71 @@
+#![allow(clippy::indexing_slicing, clippy::unwrap_used)]
+
+mod common;
+
+use eagle::build_info::BuildInfo;
+use predicates::prelude::PredicateBooleanExt;
+use predicates::str::contains;
+
+#[test]
+fn version_build_prints_build_details() {
+	common::eagle()
+		.args(["version", "--build"])
+		.assert()
+		.success()
+		.stdout(contains("commit"))
+		.stdout(contains(BuildInfo::CURRENT.target))
+		.stdout(contains("rustc "));
+}
+
+#[test]
+fn global_verbose_applies_to_version() {
+	common::eagle()
+		.env("EAGLE_LOG", "info")
+		.args(["version", "-v"])
+		.assert()
+		.success()
+		.stdout(contains("commit").not())
+		.stderr(contains("[dbg]"));
+}
+
+#[test]
+fn json_version_includes_build_details() {
+	let output = common::eagle()
+		.args(["--json", "version"])
+		.output()
+		.unwrap();
+	let doc: serde_json::Value =
+		serde_json::from_slice(&output.stdout).unwrap();
+	let build = &doc["result"]["build"];
+
+	assert_eq!(build["target"], BuildInfo::CURRENT.target);
+	assert!(build["rustc"].as_str().unwrap().starts_with("rustc "));
+	assert!(build["built_at"].as_str().unwrap().ends_with('Z'));
+	assert!(build["features"].is_array());
+}
+
+#[test]
+fn commit_label_marks_dirty_and_unknown_builds() {
+	let build = BuildInfo {
+		commit: "abc123def456",
+		dirty: true,
+		..BuildInfo::CURRENT
+	};
+	assert_eq!(build.commit_label(), "abc123def456-dirty");
+	assert_eq!(
+		BuildInfo {
+			commit: "",
+			..build
+		}
+		.commit_label(),
+		"unknown"
+	);
+	assert_eq!(
+		BuildInfo {
+			timestamp: 0,
+			..build
+		}
+		.built_at(),
+		"1970-01-01T00:00:00Z"
+	);
+}